@@ -0,0 +1,266 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use simurail::attribute::*;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CHARACTERS: usize = 4;
+const STATS: usize = 30;
+
+struct Stat {
+    base: Entity,
+    final_: Entity,
+}
+
+fn scalar(value: f64) -> Scalar {
+    Scalar::from_f64(value)
+}
+
+fn spawn_stat(world: &mut World, seed: usize) -> Stat {
+    let base = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+    world.spawn(Modifier::new(
+        base,
+        Scalar::ZERO,
+        scalar(500.0 + seed as f64),
+    ));
+    let delta = world.spawn(Attribute::BasedOn(base)).id();
+    world.spawn(Modifier::new(delta, scalar(0.432), scalar(352.0)));
+    world.spawn(Modifier::new(delta, scalar(0.12), Scalar::ZERO));
+    let extra = world.spawn(Attribute::BasedOn(base)).id();
+    world.spawn(Modifier::new(extra, scalar(0.228), scalar(200.0)));
+    let safe = world
+        .spawn(Attribute::Merged(EntityHashSet::from_iter([base, delta])))
+        .id();
+    let final_ = world
+        .spawn(Attribute::Merged(EntityHashSet::from_iter([safe, extra])))
+        .id();
+    Stat { base, final_ }
+}
+
+fn spawn_team(world: &mut World) -> Vec<Stat> {
+    let stats = (0..CHARACTERS * STATS)
+        .map(|seed| spawn_stat(world, seed))
+        .collect();
+    world.flush();
+    stats
+}
+
+fn team_world() -> (World, Vec<Stat>) {
+    let mut world = World::new();
+    world.init_resource::<AttributeGeneration>();
+    world.init_resource::<AttributeEvaluator>();
+    let stats = spawn_team(&mut world);
+    (world, stats)
+}
+
+fn plugin_world() -> App {
+    let mut app = App::new();
+    app.add_plugins(AttributePlugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn invalidate_team(world: &mut World, stats: &[Stat]) {
+    for stat in stats {
+        world
+            .entity_mut(stat.base)
+            .insert(AttributeValue::new(None));
+    }
+    world.flush();
+}
+
+fn bench_team_evaluation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("team_evaluation");
+
+    group.bench_function("fresh_evaluator_per_fetch", |b| {
+        let (mut world, stats) = team_world();
+        let mut state = SystemState::<AttributeQueries>::new(&mut world);
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                invalidate_team(&mut world, &stats);
+                let start = Instant::now();
+                let mut queries = state.get_mut(&mut world);
+                for stat in &stats {
+                    let mut evaluator = AttributeEvaluator::default();
+                    black_box(evaluator.fetch_value(&mut queries, stat.final_));
+                }
+                elapsed += start.elapsed();
+            }
+            elapsed
+        });
+    });
+
+    group.bench_function("persistent_evaluator", |b| {
+        let (mut world, stats) = team_world();
+        let mut state =
+            SystemState::<(AttributeQueries, ResMut<AttributeEvaluator>)>::new(&mut world);
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                invalidate_team(&mut world, &stats);
+                let start = Instant::now();
+                let (mut queries, mut evaluator) = state.get_mut(&mut world);
+                for stat in &stats {
+                    black_box(evaluator.fetch_value(&mut queries, stat.final_));
+                }
+                elapsed += start.elapsed();
+            }
+            elapsed
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_modifier_churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("modifier_churn");
+    for count in [1, 10, 100, 1000] {
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, &count| {
+            let mut app = plugin_world();
+            let world = app.world_mut();
+            let stat = spawn_stat(world, 0);
+            for _ in 0..count {
+                world.spawn(Modifier::new(stat.base, scalar(0.01), scalar(1.0)));
+            }
+            world.flush();
+            let mut state =
+                SystemState::<(AttributeQueries, ResMut<AttributeEvaluator>)>::new(world);
+            b.iter(|| {
+                let modifier = world
+                    .spawn(Modifier::new(stat.base, scalar(0.1), scalar(10.0)))
+                    .id();
+                world.flush();
+                let (mut queries, mut evaluator) = state.get_mut(world);
+                black_box(evaluator.fetch_value(&mut queries, stat.final_));
+                world.despawn(modifier);
+                world.flush();
+                let (mut queries, mut evaluator) = state.get_mut(world);
+                black_box(evaluator.fetch_value(&mut queries, stat.final_));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_invalidation_depth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("invalidation_depth");
+    for depth in [1, 4, 16, 64] {
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, &depth| {
+            let mut app = plugin_world();
+            let world = app.world_mut();
+            let root = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+            let root_modifier = world
+                .spawn(Modifier::new(root, Scalar::ZERO, scalar(100.0)))
+                .id();
+            let mut tail = root;
+            for _ in 0..depth {
+                tail = world.spawn(Attribute::BasedOn(tail)).id();
+                world.spawn(Modifier::new(tail, scalar(1.01), scalar(1.0)));
+            }
+            world.flush();
+            let mut state =
+                SystemState::<(AttributeQueries, ResMut<AttributeEvaluator>)>::new(world);
+            let mut delta = 100.0;
+            b.iter(|| {
+                delta += 1.0;
+                world.entity_mut(root_modifier).insert(ModifierValue {
+                    ratio: Scalar::ZERO,
+                    delta: scalar(delta),
+                });
+                world.flush();
+                let (mut queries, mut evaluator) = state.get_mut(world);
+                black_box(evaluator.fetch_value(&mut queries, tail));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_dynamic_modifier_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dynamic_modifier_chain");
+    for length in [1, 4, 16, 64] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(length),
+            &length,
+            |b, &length| {
+                let mut app = plugin_world();
+                let world = app.world_mut();
+                let root = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+                let root_modifier = world
+                    .spawn(Modifier::new(root, Scalar::ZERO, scalar(100.0)))
+                    .id();
+                world.flush();
+                let mut tail = root;
+                for _ in 0..length {
+                    let next = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+                    world.flush();
+                    world.spawn(DynamicModifier::new_scale(
+                        next,
+                        tail,
+                        Scalar::ZERO,
+                        Scalar::ZERO,
+                        scalar(0.5),
+                    ));
+                    world.flush();
+                    tail = next;
+                }
+                let mut state =
+                    SystemState::<(AttributeQueries, ResMut<AttributeEvaluator>)>::new(world);
+                let mut delta = 100.0;
+                b.iter(|| {
+                    delta += 1.0;
+                    world.entity_mut(root_modifier).insert(ModifierValue {
+                        ratio: Scalar::ZERO,
+                        delta: scalar(delta),
+                    });
+                    world.flush();
+                    let (mut queries, mut evaluator) = state.get_mut(world);
+                    black_box(evaluator.fetch_value(&mut queries, tail));
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_team_churn(c: &mut Criterion) {
+    c.bench_function("team_churn", |b| {
+        let mut app = plugin_world();
+        let world = app.world_mut();
+        let stats = spawn_team(world);
+        let mut state = SystemState::<(AttributeQueries, ResMut<AttributeEvaluator>)>::new(world);
+        b.iter(|| {
+            let modifiers = stats
+                .iter()
+                .map(|stat| {
+                    world
+                        .spawn(Modifier::new(stat.base, scalar(0.1), scalar(10.0)))
+                        .id()
+                })
+                .collect::<Vec<_>>();
+            world.flush();
+            let (mut queries, mut evaluator) = state.get_mut(world);
+            for stat in &stats {
+                black_box(evaluator.fetch_value(&mut queries, stat.final_));
+            }
+            for modifier in modifiers {
+                world.despawn(modifier);
+            }
+            world.flush();
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_team_evaluation,
+    bench_modifier_churn,
+    bench_invalidation_depth,
+    bench_dynamic_modifier_chain,
+    bench_team_churn
+);
+criterion_main!(benches);
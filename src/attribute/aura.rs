@@ -0,0 +1,223 @@
+use crate::attribute::{
+    Attribute, AttributeType, AttributeZone, Defeated, DynamicModifier, Modifier, ModifierValue,
+    OwnedAttributes, Scalar, TeamMembers,
+};
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Applies a modifier to every attribute of type `attribute_type` in zone `Z`
+/// owned by an undefeated member of `team`.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct AuraModifier<Z: AttributeZone> {
+    pub team: Entity,
+    pub attribute_type: AttributeType,
+    pub effect: AuraEffect,
+    pub zone: PhantomData<Z>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuraEffect {
+    Static(ModifierValue),
+    Dynamic(DynamicModifier),
+}
+
+impl<Z: AttributeZone> AuraModifier<Z> {
+    pub fn new(team: Entity, attribute_type: AttributeType, ratio: Scalar, delta: Scalar) -> Self {
+        Self {
+            team,
+            attribute_type,
+            effect: AuraEffect::Static(ModifierValue { ratio, delta }),
+            zone: PhantomData,
+        }
+    }
+
+    pub fn new_dynamic(
+        team: Entity,
+        attribute_type: AttributeType,
+        dynamic_modifier: DynamicModifier,
+    ) -> Self {
+        Self {
+            team,
+            attribute_type,
+            effect: AuraEffect::Dynamic(dynamic_modifier),
+            zone: PhantomData,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[relationship(relationship_target = AuraModifiers)]
+#[component(immutable)]
+pub struct AuraModifierOf(pub Entity);
+
+#[derive(Component, Deref, Default, Clone, Debug, PartialEq, Eq)]
+#[relationship_target(relationship = AuraModifierOf, linked_spawn)]
+pub struct AuraModifiers(EntityHashSet);
+
+pub fn sync_aura_modifiers<Z: AttributeZone>(
+    auras: Query<(Entity, &AuraModifier<Z>, Option<&AuraModifiers>)>,
+    teams: Query<&TeamMembers>,
+    characters: Query<&OwnedAttributes, Without<Defeated>>,
+    attributes: Query<&AttributeType, (With<Attribute>, With<Z>)>,
+    aura_modifiers: Query<(&Modifier, Option<&ModifierValue>, Option<&DynamicModifier>)>,
+    mut commands: Commands,
+) {
+    for (aura_entity, aura, existing_modifiers) in &auras {
+        let mut targets = teams
+            .get(aura.team)
+            .into_iter()
+            .flat_map(|members| members.iter())
+            .filter_map(|member| characters.get(member).ok())
+            .flat_map(|owned_attributes| owned_attributes.iter())
+            .filter(|attribute| {
+                attributes
+                    .get(*attribute)
+                    .is_ok_and(|attribute_type| *attribute_type == aura.attribute_type)
+            })
+            .collect::<EntityHashSet>();
+        for modifier_entity in existing_modifiers.into_iter().flat_map(|m| m.iter()) {
+            let Ok((modifier, value, dynamic_modifier)) = aura_modifiers.get(modifier_entity)
+            else {
+                continue;
+            };
+            let up_to_date = match aura.effect {
+                AuraEffect::Static(effect) => dynamic_modifier.is_none() && value == Some(&effect),
+                AuraEffect::Dynamic(effect) => dynamic_modifier == Some(&effect),
+            };
+            if !up_to_date || !targets.remove(&modifier.0) {
+                commands.entity(modifier_entity).despawn();
+            }
+        }
+        for target in targets {
+            let mut modifier = commands.spawn((Modifier(target), AuraModifierOf(aura_entity)));
+            match aura.effect {
+                AuraEffect::Static(value) => {
+                    modifier.insert(value);
+                }
+                AuraEffect::Dynamic(dynamic_modifier) => {
+                    modifier.insert(dynamic_modifier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{
+        AttributeOf, AttributePlugin, AttributeValue, BaseZoneAttribute, DynamicModifierType,
+        ExtraZoneAttribute, Number, TeamMember,
+    };
+
+    #[test]
+    fn test_aura_follows_team_membership() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let team = world.spawn_empty().id();
+        let spawn_character = |world: &mut World| {
+            let character = world.spawn(TeamMember(team)).id();
+            let extra_attack = world
+                .spawn((
+                    Attribute::Plain(Scalar::ZERO),
+                    AttributeType::Attack,
+                    ExtraZoneAttribute,
+                    AttributeOf(character),
+                ))
+                .id();
+            let base_attack = world
+                .spawn((
+                    Attribute::Plain(Scalar::ZERO),
+                    AttributeType::Attack,
+                    BaseZoneAttribute,
+                    AttributeOf(character),
+                ))
+                .id();
+            let extra_defense = world
+                .spawn((
+                    Attribute::Plain(Scalar::ZERO),
+                    AttributeType::Defense,
+                    ExtraZoneAttribute,
+                    AttributeOf(character),
+                ))
+                .id();
+            (character, [extra_attack, base_attack, extra_defense])
+        };
+        let (robin, robin_attributes) = spawn_character(world);
+        let (danheng, danheng_attributes) = spawn_character(world);
+        let aura = world
+            .spawn(AuraModifier::<ExtraZoneAttribute>::new(
+                team,
+                AttributeType::Attack,
+                Scalar::ZERO,
+                Scalar::from_f64(200.0),
+            ))
+            .id();
+        app.update();
+
+        let values = |app: &App, attributes: [Entity; 3]| {
+            attributes.map(|entity| app.world().get::<AttributeValue>(entity).unwrap().0)
+        };
+        assert_eq!(
+            values(&app, robin_attributes),
+            [
+                Some(Scalar::from_f64(200.0)),
+                Some(Scalar::ZERO),
+                Some(Scalar::ZERO)
+            ]
+        );
+        assert_eq!(
+            values(&app, danheng_attributes),
+            [
+                Some(Scalar::from_f64(200.0)),
+                Some(Scalar::ZERO),
+                Some(Scalar::ZERO)
+            ]
+        );
+
+        let (_, phainon_attributes) = spawn_character(app.world_mut());
+        app.world_mut().entity_mut(danheng).insert(Defeated);
+        app.update();
+        assert_eq!(
+            values(&app, phainon_attributes)[0],
+            Some(Scalar::from_f64(200.0))
+        );
+        assert_eq!(values(&app, danheng_attributes)[0], Some(Scalar::ZERO));
+        assert_eq!(app.world().get::<AuraModifiers>(aura).unwrap().len(), 2);
+
+        app.world_mut().entity_mut(robin).remove::<TeamMember>();
+        app.world_mut()
+            .entity_mut(aura)
+            .insert(AuraModifier::<ExtraZoneAttribute>::new_dynamic(
+                team,
+                AttributeType::Attack,
+                DynamicModifier {
+                    source: robin_attributes[1],
+                    threshold: Scalar::ZERO,
+                    ratio: Scalar::ZERO,
+                    delta: Scalar::from_f64(0.5),
+                    modifier_type: DynamicModifierType::Scale,
+                },
+            ));
+        app.world_mut().spawn(Modifier::new(
+            robin_attributes[1],
+            Scalar::ZERO,
+            Scalar::from_f64(100.0),
+        ));
+        app.update();
+        assert_eq!(values(&app, robin_attributes)[0], Some(Scalar::ZERO));
+        assert_eq!(
+            values(&app, phainon_attributes)[0],
+            Some(Scalar::from_f64(50.0))
+        );
+
+        app.world_mut().despawn(aura);
+        app.update();
+        assert_eq!(values(&app, phainon_attributes)[0], Some(Scalar::ZERO));
+    }
+}
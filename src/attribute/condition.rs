@@ -0,0 +1,176 @@
+use crate::attribute::modifier::invalidate_modifier_target;
+use crate::attribute::{
+    AttributeEvaluator, AttributeQueries, Modifier, Scalar, Tag, Tags, bump_values_generation,
+};
+use bevy::ecs::archetype::Archetypes;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::entity::Entities;
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+/// Gates a modifier: while the condition is false the modifier is marked
+/// [`InactiveModifier`] and contributes nothing to its target. Panel values
+/// follow [`sync_modifier_conditions`];
+/// [`AttributeEvaluator::fetch_value_in_context`] checks the condition itself,
+/// so combat calculations see changes made earlier in the same frame.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub enum ModifierCondition {
+    HasComponent(Entity, ComponentId),
+    HasTag(Entity, Tag),
+    AttributeAtLeast(Entity, Scalar),
+    AttributeBelow(Entity, Scalar),
+    Not(Box<ModifierCondition>),
+    All(Vec<ModifierCondition>),
+    Any(Vec<ModifierCondition>),
+}
+
+impl ModifierCondition {
+    pub fn has_component<C: Component>(world: &mut World, entity: Entity) -> Self {
+        Self::HasComponent(entity, world.register_component::<C>())
+    }
+
+    pub fn evaluate(
+        &self,
+        condition_queries: &ModifierConditionQueries,
+        attribute_queries: &mut AttributeQueries,
+        attribute_evaluator: &mut AttributeEvaluator,
+    ) -> bool {
+        match self {
+            ModifierCondition::HasComponent(entity, component_id) => condition_queries
+                .entities
+                .get(*entity)
+                .is_some_and(|location| {
+                    condition_queries.archetypes[location.archetype_id].contains(*component_id)
+                }),
+            ModifierCondition::HasTag(entity, tag) => condition_queries
+                .tags
+                .get(*entity)
+                .is_ok_and(|tags| tags.contains(tag)),
+            ModifierCondition::AttributeAtLeast(attribute, threshold) => attribute_evaluator
+                .fetch_value(attribute_queries, *attribute)
+                .is_some_and(|value| value >= *threshold),
+            ModifierCondition::AttributeBelow(attribute, threshold) => attribute_evaluator
+                .fetch_value(attribute_queries, *attribute)
+                .is_some_and(|value| value < *threshold),
+            ModifierCondition::Not(condition) => {
+                !condition.evaluate(condition_queries, attribute_queries, attribute_evaluator)
+            }
+            ModifierCondition::All(conditions) => conditions.iter().all(|condition| {
+                condition.evaluate(condition_queries, attribute_queries, attribute_evaluator)
+            }),
+            ModifierCondition::Any(conditions) => conditions.iter().any(|condition| {
+                condition.evaluate(condition_queries, attribute_queries, attribute_evaluator)
+            }),
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ModifierConditionQueries<'w, 's> {
+    pub entities: &'w Entities,
+    pub archetypes: &'w Archetypes,
+    pub tags: Query<'w, 's, &'static Tags>,
+    pub conditions: Query<'w, 's, &'static ModifierCondition>,
+}
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[component(on_insert = inactive_modifier_on_change)]
+#[component(on_remove = inactive_modifier_on_change)]
+pub struct InactiveModifier;
+
+fn inactive_modifier_on_change(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
+    if let Some(modifier) = world.get::<Modifier>(entity) {
+        let target_entity = modifier.0;
+        invalidate_modifier_target(&mut world, target_entity);
+    }
+}
+
+pub fn sync_modifier_conditions(
+    conditional_modifiers: Query<(Entity, &ModifierCondition, Has<InactiveModifier>)>,
+    condition_queries: ModifierConditionQueries,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+    mut commands: Commands,
+) {
+    for (entity, condition, inactive) in &conditional_modifiers {
+        let active = condition.evaluate(
+            &condition_queries,
+            &mut attribute_queries,
+            &mut attribute_evaluator,
+        );
+        if active && inactive {
+            commands.entity(entity).remove::<InactiveModifier>();
+        } else if !active && !inactive {
+            commands.entity(entity).insert(InactiveModifier);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, AttributeValue, ModifierValue, Number};
+
+    #[derive(Component)]
+    struct WeaknessBroken;
+
+    #[test]
+    fn test_conditional_modifiers() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let enemy = world.spawn_empty().id();
+        let character = world.spawn_empty().id();
+        let hp_ratio = world.spawn(Attribute::Plain(Scalar::ONE)).id();
+        let hp_ratio_modifier = world
+            .spawn(Modifier::new(hp_ratio, Scalar::ONE, Scalar::ZERO))
+            .id();
+        let damage = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn(Modifier::new(damage, Scalar::ONE, Scalar::ZERO));
+        let broken_condition = ModifierCondition::has_component::<WeaknessBroken>(world, enemy);
+        world.spawn((
+            Modifier::new(damage, Scalar::from_f64(0.25), Scalar::ZERO),
+            broken_condition,
+        ));
+        world.spawn((
+            Modifier::new(damage, Scalar::from_f64(0.5), Scalar::ZERO),
+            ModifierCondition::All(vec![
+                ModifierCondition::HasTag(character, Tag("enhanced")),
+                ModifierCondition::Not(Box::new(ModifierCondition::AttributeBelow(
+                    hp_ratio,
+                    Scalar::from_f64(0.5),
+                ))),
+            ]),
+        ));
+        app.update();
+
+        let value = |app: &App| app.world().get::<AttributeValue>(damage).unwrap().0;
+        assert_eq!(value(&app), Some(Scalar::from_f64(100.0)));
+
+        app.world_mut().entity_mut(enemy).insert(WeaknessBroken);
+        app.update();
+        assert_eq!(value(&app), Some(Scalar::from_f64(125.0)));
+
+        app.world_mut()
+            .entity_mut(character)
+            .insert(Tags::new([Tag("enhanced")]));
+        app.update();
+        assert_eq!(value(&app), Some(Scalar::from_f64(175.0)));
+
+        app.world_mut()
+            .entity_mut(hp_ratio_modifier)
+            .insert(ModifierValue {
+                ratio: Scalar::from_f64(0.25),
+                delta: Scalar::ZERO,
+            });
+        app.world_mut().entity_mut(enemy).remove::<WeaknessBroken>();
+        app.update();
+        assert_eq!(value(&app), Some(Scalar::from_f64(100.0)));
+    }
+}
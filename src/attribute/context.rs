@@ -0,0 +1,220 @@
+use crate::attribute::modifier::invalidate_modifier_target;
+use crate::attribute::{Modifier, Tags, bump_values_generation};
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+/// The attacker–target pair and action an attribute is evaluated for, see
+/// [`AttributeEvaluator::fetch_value_in_context`](crate::attribute::AttributeEvaluator::fetch_value_in_context).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationContext {
+    pub attacker: Option<Entity>,
+    pub target: Option<Entity>,
+    pub target_tags: Tags,
+    pub action_tags: Tags,
+}
+
+impl EvaluationContext {
+    pub fn new(attacker: Entity, target: Entity) -> Self {
+        Self {
+            attacker: Some(attacker),
+            target: Some(target),
+            ..default()
+        }
+    }
+
+    pub fn with_target_tags(mut self, target_tags: Tags) -> Self {
+        self.target_tags = target_tags;
+        self
+    }
+
+    pub fn with_action_tags(mut self, action_tags: Tags) -> Self {
+        self.action_tags = action_tags;
+        self
+    }
+}
+
+/// Restricts a modifier to the evaluation contexts it matches. Such modifiers
+/// never contribute to panel values.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+#[component(on_insert = modifier_context_on_change)]
+#[component(on_remove = modifier_context_on_change)]
+pub struct ModifierContext {
+    pub attacker: Option<Entity>,
+    pub target: Option<Entity>,
+    pub target_tags: Tags,
+    pub action_tags: Tags,
+}
+
+impl ModifierContext {
+    pub fn with_attacker(mut self, attacker: Entity) -> Self {
+        self.attacker = Some(attacker);
+        self
+    }
+
+    pub fn with_target(mut self, target: Entity) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_target_tags(mut self, target_tags: Tags) -> Self {
+        self.target_tags = target_tags;
+        self
+    }
+
+    pub fn with_action_tags(mut self, action_tags: Tags) -> Self {
+        self.action_tags = action_tags;
+        self
+    }
+
+    pub fn applies_to(&self, context: &EvaluationContext) -> bool {
+        self.attacker
+            .is_none_or(|attacker| context.attacker == Some(attacker))
+            && self
+                .target
+                .is_none_or(|target| context.target == Some(target))
+            && self.target_tags.is_subset(&context.target_tags)
+            && self.action_tags.is_subset(&context.action_tags)
+    }
+}
+
+fn modifier_context_on_change(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
+    if let Some(modifier) = world.get::<Modifier>(entity) {
+        let target_entity = modifier.0;
+        invalidate_modifier_target(&mut world, target_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{
+        Attribute, AttributeEvaluator, AttributePlugin, AttributeQueries, AttributeValue,
+        DynamicModifier, ModifierConditionQueries, Number, Scalar, Tag,
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_fetch_value_in_context() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let attacker = world.spawn_empty().id();
+        let other_attacker = world.spawn_empty().id();
+        let target = world.spawn_empty().id();
+        let damage_bonus = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.spawn(Modifier::new(
+            damage_bonus,
+            Scalar::ZERO,
+            Scalar::from_f64(0.1),
+        ));
+        world.spawn((
+            Modifier::new(damage_bonus, Scalar::ZERO, Scalar::from_f64(0.3)),
+            ModifierContext::default().with_action_tags(Tags::new([Tag("ultimate")])),
+        ));
+        world.spawn((
+            Modifier::new(damage_bonus, Scalar::ZERO, Scalar::from_f64(0.2)),
+            ModifierContext::default()
+                .with_attacker(attacker)
+                .with_target_tags(Tags::new([Tag("burning")])),
+        ));
+        let total_bonus = world.spawn(Attribute::BasedOn(damage_bonus)).id();
+        world.spawn(Modifier::new(total_bonus, Scalar::ONE, Scalar::ONE));
+        let ultimate = || ModifierContext::default().with_action_tags(Tags::new([Tag("ultimate")]));
+        let energy = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.spawn(Modifier::new(energy, Scalar::ZERO, Scalar::from_f64(100.0)));
+        world.spawn((
+            Modifier::new(energy, Scalar::ZERO, Scalar::from_f64(100.0)),
+            ultimate(),
+        ));
+        let level = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.spawn(Modifier::new(level, Scalar::ZERO, Scalar::from_f64(3.0)));
+        world.spawn((Modifier::new(level, Scalar::ZERO, Scalar::ONE), ultimate()));
+        let scaled = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.flush();
+        world.spawn(DynamicModifier::new_scale(
+            scaled,
+            energy,
+            Scalar::ZERO,
+            Scalar::ZERO,
+            Scalar::from_f64(0.5),
+        ));
+        app.update();
+
+        let fetch_attribute = |app: &mut App, attribute: Entity, context: EvaluationContext| {
+            app.world_mut()
+                .run_system_once(
+                    move |mut queries: AttributeQueries,
+                          condition_queries: ModifierConditionQueries,
+                          mut evaluator: ResMut<AttributeEvaluator>| {
+                        evaluator.fetch_value_in_context(
+                            &mut queries,
+                            &condition_queries,
+                            attribute,
+                            &context,
+                        )
+                    },
+                )
+                .unwrap()
+        };
+        let fetch = |app: &mut App, context| fetch_attribute(app, total_bonus, context);
+        let panel = |app: &App| app.world().get::<AttributeValue>(total_bonus).unwrap().0;
+        assert_eq!(panel(&app), Some(Scalar::from_f64(1.1)));
+        assert_eq!(
+            fetch(&mut app, EvaluationContext::new(attacker, target)),
+            Some(Scalar::from_f64(1.1))
+        );
+        assert_eq!(
+            fetch(
+                &mut app,
+                EvaluationContext::new(attacker, target)
+                    .with_action_tags(Tags::new([Tag("ultimate"), Tag("attack")]))
+                    .with_target_tags(Tags::new([Tag("burning")]))
+            ),
+            Some(Scalar::from_f64(1.6))
+        );
+        assert_eq!(
+            fetch(
+                &mut app,
+                EvaluationContext::new(other_attacker, target)
+                    .with_target_tags(Tags::new([Tag("burning")]))
+            ),
+            Some(Scalar::from_f64(1.1))
+        );
+        assert_eq!(panel(&app), Some(Scalar::from_f64(1.1)));
+
+        // Dynamic modifiers follow their sources evaluated in the context,
+        // including sources added after the order was cached.
+        let ultimate_context = || {
+            EvaluationContext::new(attacker, target).with_action_tags(Tags::new([Tag("ultimate")]))
+        };
+        assert_eq!(
+            fetch_attribute(&mut app, scaled, EvaluationContext::new(attacker, target)),
+            Some(Scalar::from_f64(50.0))
+        );
+        assert_eq!(
+            fetch_attribute(&mut app, scaled, ultimate_context()),
+            Some(Scalar::from_f64(100.0))
+        );
+        app.world_mut().spawn(DynamicModifier::new_scale(
+            scaled,
+            level,
+            Scalar::ZERO,
+            Scalar::ZERO,
+            Scalar::ONE,
+        ));
+        app.update();
+        assert_eq!(
+            app.world().get::<AttributeValue>(scaled).unwrap().0,
+            Some(Scalar::from_f64(53.0))
+        );
+        assert_eq!(
+            fetch_attribute(&mut app, scaled, ultimate_context()),
+            Some(Scalar::from_f64(104.0))
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::attribute::modifier::fetch_value_in_hook;
+use crate::attribute::{
+    Attribute, AttributeDependents, AttributeValue, DependencyAttributeDirtyEvent, DynamicModifier,
+    ModifierValue, Number, Scalar,
+};
+use bevy::ecs::error::CommandWithEntity;
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+/// What happens to an attribute or dynamic modifier when an attribute it
+/// depends on is despawned.
+///
+/// Set it on the dependent to override [`DefaultDependencyDespawnPolicy`].
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[component(immutable)]
+pub enum DependencyDespawnPolicy {
+    /// Despawn the dependent as well.
+    Cascade,
+    /// Treat the despawned dependency as zero: `BasedOn` becomes `Plain(0)`,
+    /// `Merged` drops the member and a dynamic modifier keeps the value it
+    /// would have for a zero source.
+    #[default]
+    Zero,
+    /// Keep the last known value: `BasedOn` becomes `Plain` with the base's
+    /// last value, `Merged` becomes `Fixed` with its own last value and a
+    /// dynamic modifier keeps its current `ModifierValue`.
+    Freeze,
+    /// Leave the dependent pointing at the despawned entity, evaluate the
+    /// missing dependency as zero and trigger
+    /// [`DanglingAttributeDependencyEvent`].
+    Error,
+}
+
+#[derive(Resource, Deref, DerefMut, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DefaultDependencyDespawnPolicy(pub DependencyDespawnPolicy);
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DanglingAttributeDependencyEvent {
+    pub entity: Entity,
+    pub dependency: Entity,
+}
+
+pub(super) fn attribute_dependents_on_despawn(
+    mut world: DeferredWorld,
+    HookContext { entity, .. }: HookContext,
+) {
+    let dependent_entities = world
+        .get::<AttributeDependents>(entity)
+        .unwrap()
+        .0
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    let default_policy = world
+        .get_resource::<DefaultDependencyDespawnPolicy>()
+        .copied()
+        .unwrap_or_default()
+        .0;
+    let mut last_value = None;
+    for dependent_entity in dependent_entities {
+        let policy = world
+            .get::<DependencyDespawnPolicy>(dependent_entity)
+            .copied()
+            .unwrap_or(default_policy);
+        match policy {
+            DependencyDespawnPolicy::Cascade => {
+                world.commands().entity(dependent_entity).try_despawn();
+            }
+            DependencyDespawnPolicy::Zero => {
+                let command = move |dependent: EntityWorldMut| {
+                    replace_despawned_dependency(dependent, entity, None);
+                };
+                world
+                    .commands()
+                    .queue_silenced(command.with_entity(dependent_entity));
+            }
+            DependencyDespawnPolicy::Freeze => {
+                let dependency_value = *last_value.get_or_insert_with(|| {
+                    fetch_value_in_hook(&mut world, entity).unwrap_or(Scalar::ZERO)
+                });
+                let dependent_value = match world.get::<Attribute>(dependent_entity) {
+                    Some(Attribute::Merged(_)) => {
+                        fetch_value_in_hook(&mut world, dependent_entity).unwrap_or(Scalar::ZERO)
+                    }
+                    _ => Scalar::ZERO,
+                };
+                let command = move |dependent: EntityWorldMut| {
+                    replace_despawned_dependency(
+                        dependent,
+                        entity,
+                        Some((dependency_value, dependent_value)),
+                    );
+                };
+                world
+                    .commands()
+                    .queue_silenced(command.with_entity(dependent_entity));
+            }
+            DependencyDespawnPolicy::Error => {
+                world.trigger(DanglingAttributeDependencyEvent {
+                    entity: dependent_entity,
+                    dependency: entity,
+                });
+                let command = |mut dependent: EntityWorldMut| {
+                    if dependent
+                        .get::<Attribute>()
+                        .is_some_and(|attribute| !matches!(attribute, Attribute::Fixed))
+                    {
+                        dependent.insert(AttributeValue::new(None));
+                    }
+                };
+                world
+                    .commands()
+                    .queue_silenced(command.with_entity(dependent_entity));
+                world
+                    .commands()
+                    .trigger(DependencyAttributeDirtyEvent(dependent_entity));
+            }
+        }
+    }
+}
+
+fn replace_despawned_dependency(
+    mut dependent: EntityWorldMut,
+    dependency_entity: Entity,
+    frozen_values: Option<(Scalar, Scalar)>,
+) {
+    match dependent.get::<Attribute>() {
+        Some(Attribute::BasedOn(base_entity)) if *base_entity == dependency_entity => {
+            let base = frozen_values.map_or(Scalar::ZERO, |(dependency_value, _)| dependency_value);
+            dependent.insert(Attribute::Plain(base));
+        }
+        Some(Attribute::Merged(dependency_entities))
+            if dependency_entities.contains(&dependency_entity) =>
+        {
+            if let Some((_, dependent_value)) = frozen_values {
+                dependent.insert((AttributeValue::new(Some(dependent_value)), Attribute::Fixed));
+            } else {
+                let mut dependency_entities = dependency_entities.clone();
+                dependency_entities.remove(&dependency_entity);
+                dependent.insert(Attribute::Merged(dependency_entities));
+            }
+        }
+        _ => {}
+    }
+    if let Some(dynamic_modifier) = dependent.get::<DynamicModifier>().copied()
+        && dynamic_modifier.source == dependency_entity
+    {
+        if frozen_values.is_none() {
+            let (ratio, delta) = dynamic_modifier.value(Scalar::ZERO);
+            dependent.insert(ModifierValue { ratio, delta });
+        }
+        dependent.remove::<DynamicModifier>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{AttributePlugin, Modifier, validate_attribute_graph};
+    use bevy::ecs::entity::EntityHashSet;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct DanglingDependencies(Vec<(Entity, Entity)>);
+
+    #[test]
+    fn test_dependency_despawn_policies() {
+        for policy in [
+            DependencyDespawnPolicy::Cascade,
+            DependencyDespawnPolicy::Zero,
+            DependencyDespawnPolicy::Freeze,
+            DependencyDespawnPolicy::Error,
+        ] {
+            let mut app = App::new();
+            app.add_plugins(AttributePlugin);
+            app.insert_resource(DefaultDependencyDespawnPolicy(policy));
+            app.init_resource::<DanglingDependencies>();
+            app.add_observer(
+                |event: On<DanglingAttributeDependencyEvent>,
+                 mut dangling: ResMut<DanglingDependencies>| {
+                    dangling.0.push((event.entity, event.dependency));
+                },
+            );
+            app.finish();
+            app.cleanup();
+            let world = app.world_mut();
+
+            let base = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+            world.spawn(Modifier::new(base, Scalar::ZERO, Scalar::from_f64(100.0)));
+            let based = world.spawn(Attribute::BasedOn(base)).id();
+            world.spawn(Modifier::new(based, Scalar::ONE, Scalar::from_f64(5.0)));
+            let other = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+            world.spawn(Modifier::new(other, Scalar::ZERO, Scalar::from_f64(20.0)));
+            let merged = world
+                .spawn(Attribute::Merged(EntityHashSet::from_iter([base, other])))
+                .id();
+            let scaled = world.spawn(Attribute::Plain(Scalar::from_f64(10.0))).id();
+            world.spawn(Modifier::new(scaled, Scalar::ONE, Scalar::ZERO));
+            let dynamic_modifier = world
+                .spawn(DynamicModifier::new_scale(
+                    scaled,
+                    base,
+                    Scalar::ZERO,
+                    Scalar::from_f64(0.01),
+                    Scalar::ZERO,
+                ))
+                .id();
+            app.update();
+            let world = app.world_mut();
+            let value = |world: &World, entity| world.get::<AttributeValue>(entity).unwrap().0;
+            assert_eq!(value(world, based), Some(Scalar::from_f64(105.0)));
+            assert_eq!(value(world, merged), Some(Scalar::from_f64(120.0)));
+            assert_eq!(value(world, scaled), Some(Scalar::from_f64(20.0)));
+
+            world.despawn(base);
+            app.update();
+            let world = app.world_mut();
+            let discrepancies = world.run_system_once(validate_attribute_graph).unwrap();
+            match policy {
+                DependencyDespawnPolicy::Cascade => {
+                    assert!(world.get_entity(based).is_err());
+                    assert!(world.get_entity(merged).is_err());
+                    assert!(world.get_entity(dynamic_modifier).is_err());
+                    assert_eq!(value(world, other), Some(Scalar::from_f64(20.0)));
+                    assert_eq!(value(world, scaled), Some(Scalar::from_f64(10.0)));
+                    assert_eq!(discrepancies, vec![]);
+                }
+                DependencyDespawnPolicy::Zero => {
+                    assert_eq!(
+                        world.get::<Attribute>(based),
+                        Some(&Attribute::Plain(Scalar::ZERO))
+                    );
+                    assert_eq!(value(world, based), Some(Scalar::from_f64(5.0)));
+                    assert_eq!(value(world, merged), Some(Scalar::from_f64(20.0)));
+                    assert!(!world.entity(dynamic_modifier).contains::<DynamicModifier>());
+                    assert_eq!(value(world, scaled), Some(Scalar::from_f64(10.0)));
+                    assert_eq!(discrepancies, vec![]);
+                }
+                DependencyDespawnPolicy::Freeze => {
+                    assert_eq!(
+                        world.get::<Attribute>(based),
+                        Some(&Attribute::Plain(Scalar::from_f64(100.0)))
+                    );
+                    assert_eq!(value(world, based), Some(Scalar::from_f64(105.0)));
+                    assert_eq!(world.get::<Attribute>(merged), Some(&Attribute::Fixed));
+                    assert_eq!(value(world, merged), Some(Scalar::from_f64(120.0)));
+                    assert!(!world.entity(dynamic_modifier).contains::<DynamicModifier>());
+                    assert_eq!(value(world, scaled), Some(Scalar::from_f64(20.0)));
+                    assert_eq!(discrepancies, vec![]);
+                }
+                DependencyDespawnPolicy::Error => {
+                    assert_eq!(value(world, based), Some(Scalar::from_f64(5.0)));
+                    assert_eq!(value(world, merged), Some(Scalar::from_f64(20.0)));
+                    assert_eq!(value(world, scaled), Some(Scalar::from_f64(10.0)));
+                    let mut dangling = world.resource::<DanglingDependencies>().0.clone();
+                    dangling.sort();
+                    let mut expected =
+                        vec![(based, base), (merged, base), (dynamic_modifier, base)];
+                    expected.sort();
+                    assert_eq!(dangling, expected);
+                    // Reported through the event only, not as a discrepancy.
+                    assert_eq!(discrepancies, vec![]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::attribute::{Attribute, AttributeEvaluator, AttributeQueries, AttributeValue, Scalar};
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+use petgraph::algo::toposort;
+use petgraph::graph::NodeIndex;
+use petgraph::prelude::DiGraph;
+use petgraph::unionfind::UnionFind;
+
+pub fn evaluate_attributes(
+    attribute_entities: Query<Entity, (With<Attribute>, With<AttributeValue>)>,
+    mut queries: AttributeQueries,
+) {
+    let dirty_entities = attribute_entities
+        .iter()
+        .filter(|e| {
+            queries
+                .attribute_values
+                .get(*e)
+                .is_ok_and(|value| value.is_none())
+        })
+        .collect::<Vec<_>>();
+    if dirty_entities.is_empty() {
+        return;
+    }
+    let clusters = partition_clusters(&queries, &dirty_entities);
+    let task_pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    let mut buckets = vec![Vec::new(); task_pool.thread_num().clamp(1, clusters.len())];
+    let mut bucket_sizes = vec![0; buckets.len()];
+    for cluster in clusters {
+        let (index, _) = bucket_sizes
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, size)| **size)
+            .unwrap();
+        bucket_sizes[index] += cluster.len();
+        buckets[index].push(cluster);
+    }
+    let shared_queries = &queries;
+    let results = task_pool.scope(|scope| {
+        for bucket in &buckets {
+            scope.spawn(async move {
+                bucket
+                    .iter()
+                    .flat_map(|cluster| evaluate_cluster(shared_queries, cluster))
+                    .collect::<Vec<_>>()
+            });
+        }
+    });
+    for (entity, value) in results.into_iter().flatten() {
+        *queries.attribute_values.get_mut(entity).unwrap() = AttributeValue::new(Some(value));
+    }
+}
+
+fn dependencies(queries: &AttributeQueries, entity: Entity) -> Vec<Entity> {
+    match queries.attributes.get(entity) {
+        Ok((Attribute::BasedOn(base_entity), _)) => vec![*base_entity],
+        Ok((Attribute::Merged(dependency_entities), _)) => {
+            dependency_entities.iter().copied().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn partition_clusters(queries: &AttributeQueries, dirty_entities: &[Entity]) -> Vec<Vec<Entity>> {
+    let indices = dirty_entities
+        .iter()
+        .enumerate()
+        .map(|(index, entity)| (*entity, index))
+        .collect::<EntityHashMap<_>>();
+    let mut union_find = UnionFind::<usize>::new(dirty_entities.len());
+    for (index, entity) in dirty_entities.iter().enumerate() {
+        for dependency_entity in dependencies(queries, *entity) {
+            if let Some(dependency_index) = indices.get(&dependency_entity) {
+                union_find.union(index, *dependency_index);
+            }
+        }
+    }
+    let mut cluster_indices = vec![None; dirty_entities.len()];
+    let mut clusters = Vec::<Vec<Entity>>::new();
+    for (index, label) in union_find.into_labeling().into_iter().enumerate() {
+        let cluster_index = *cluster_indices[label].get_or_insert_with(|| {
+            clusters.push(Vec::new());
+            clusters.len() - 1
+        });
+        clusters[cluster_index].push(dirty_entities[index]);
+    }
+    clusters
+}
+
+fn evaluate_cluster(queries: &AttributeQueries, cluster: &[Entity]) -> Vec<(Entity, Scalar)> {
+    let mut graph = DiGraph::<Entity, ()>::with_capacity(cluster.len(), cluster.len());
+    let entity_node_map = cluster
+        .iter()
+        .map(|entity| (*entity, graph.add_node(*entity)))
+        .collect::<EntityHashMap<NodeIndex>>();
+    for entity in cluster {
+        for dependency_entity in dependencies(queries, *entity) {
+            if let Some(dependency_id) = entity_node_map.get(&dependency_entity) {
+                graph.update_edge(*dependency_id, entity_node_map[entity], ());
+            }
+        }
+    }
+    let mut values = EntityHashMap::with_capacity(cluster.len());
+    for index in toposort(&graph, None).unwrap() {
+        let entity = graph[index];
+        let value = AttributeEvaluator::evaluate(queries, entity, None, |dependency_entity| {
+            values
+                .get(&dependency_entity)
+                .copied()
+                .unwrap_or_else(|| queries.stored_value(dependency_entity))
+        });
+        values.insert(entity, value);
+    }
+    values.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{AttributeDependents, AttributePlugin, Modifier, Number};
+    use bevy::ecs::entity::EntityHashSet;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_parallel_evaluation_matches_sequential() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let mut finals = Vec::new();
+        let mut bases = Vec::new();
+        for character in 0..8 {
+            for stat in 0..10 {
+                let base = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+                world.spawn(Modifier::new(
+                    base,
+                    Scalar::ZERO,
+                    Scalar::from_f64(100.0 + character as f64 * 0.1),
+                ));
+                let delta = world.spawn(Attribute::BasedOn(base)).id();
+                world.spawn(Modifier::new(
+                    delta,
+                    Scalar::from_f64(0.432 + stat as f64 * 0.01),
+                    Scalar::from_f64(19.0),
+                ));
+                world.spawn(Modifier::new(
+                    delta,
+                    Scalar::from_f64(0.116),
+                    Scalar::from_f64(0.3),
+                ));
+                let final_ = world
+                    .spawn(Attribute::Merged(EntityHashSet::from_iter([base, delta])))
+                    .id();
+                bases.push(base);
+                finals.push(final_);
+            }
+        }
+        world.flush();
+
+        world.run_system_once(evaluate_attributes).unwrap();
+        let parallel = finals
+            .iter()
+            .map(|e| world.get::<AttributeValue>(*e).unwrap().0.unwrap())
+            .collect::<Vec<_>>();
+
+        for base in &bases {
+            world.entity_mut(*base).insert(AttributeValue::new(None));
+        }
+        world.flush();
+        assert!(world.get::<AttributeDependents>(bases[0]).is_some());
+        assert!(world.get::<AttributeValue>(finals[0]).unwrap().is_none());
+
+        let finals_clone = finals.clone();
+        let sequential = world
+            .run_system_once(
+                move |mut queries: AttributeQueries, mut evaluator: ResMut<AttributeEvaluator>| {
+                    finals_clone
+                        .iter()
+                        .map(|e| evaluator.fetch_value(&mut queries, *e).unwrap())
+                        .collect::<Vec<_>>()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            parallel
+                .into_iter()
+                .map(Number::to_bits)
+                .collect::<Vec<_>>(),
+            sequential
+                .into_iter()
+                .map(Number::to_bits)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use crate::attribute::{
+    Attribute, AttributeValue, DynamicModifier, InactiveModifier, Modifier, ModifierCondition,
+    ModifierContext, ModifierValue, Number, Scalar,
+};
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use petgraph::dot::{Config, Dot};
+use petgraph::graph::{EdgeReference, NodeIndex};
+use petgraph::prelude::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde_json::{Value, json};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeGraphNode {
+    Attribute {
+        entity: Entity,
+        name: Option<String>,
+        kind: &'static str,
+        value: Option<Scalar>,
+    },
+    Modifier {
+        entity: Entity,
+        name: Option<String>,
+        ratio: Scalar,
+        delta: Scalar,
+        dynamic: bool,
+        /// `false` while a [`ModifierCondition`] keeps the modifier inactive.
+        active: bool,
+        conditional: bool,
+        /// Whether the modifier only applies within a matching
+        /// [`EvaluationContext`](crate::attribute::EvaluationContext).
+        contextual: bool,
+    },
+    Missing(Entity),
+}
+
+impl AttributeGraphNode {
+    pub fn entity(&self) -> Entity {
+        match self {
+            AttributeGraphNode::Attribute { entity, .. } => *entity,
+            AttributeGraphNode::Modifier { entity, .. } => *entity,
+            AttributeGraphNode::Missing(entity) => *entity,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            AttributeGraphNode::Attribute {
+                entity,
+                name,
+                kind,
+                value,
+            } => {
+                let value = value.map_or("dirty".to_string(), |value| value.to_string());
+                format!(
+                    "{}\n{kind} = {value}",
+                    name.clone().unwrap_or(entity.to_string())
+                )
+            }
+            AttributeGraphNode::Modifier {
+                entity,
+                name,
+                ratio,
+                delta,
+                active,
+                ..
+            } => format!(
+                "{}\nratio {ratio} delta {delta}{}",
+                name.clone().unwrap_or(entity.to_string()),
+                if *active { "" } else { "\ninactive" }
+            ),
+            AttributeGraphNode::Missing(entity) => format!("{entity}\nmissing"),
+        }
+    }
+}
+
+/// Edges point from the entity providing a value to the one consuming it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttributeGraphEdge {
+    BasedOn,
+    Merged,
+    Modifier,
+    Source,
+}
+
+impl AttributeGraphEdge {
+    pub fn name(self) -> &'static str {
+        match self {
+            AttributeGraphEdge::BasedOn => "based_on",
+            AttributeGraphEdge::Merged => "merged",
+            AttributeGraphEdge::Modifier => "modifier",
+            AttributeGraphEdge::Source => "source",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AttributeGraphExport {
+    pub graph: DiGraph<AttributeGraphNode, AttributeGraphEdge>,
+}
+
+impl AttributeGraphExport {
+    pub fn to_dot(&self) -> String {
+        let edge_attributes = |_: &DiGraph<_, _>, edge: EdgeReference<AttributeGraphEdge>| {
+            let style = match edge.weight() {
+                AttributeGraphEdge::BasedOn | AttributeGraphEdge::Merged => "solid",
+                AttributeGraphEdge::Modifier => "dashed",
+                AttributeGraphEdge::Source => "dotted",
+            };
+            format!("label = {:?} style = {style}", edge.weight().name())
+        };
+        let node_attributes = |_: &DiGraph<_, _>, (_, node): (NodeIndex, &AttributeGraphNode)| {
+            let shape = match node {
+                AttributeGraphNode::Attribute { .. } => "box",
+                AttributeGraphNode::Modifier { .. } => "ellipse",
+                AttributeGraphNode::Missing(_) => "octagon",
+            };
+            format!("label = {:?} shape = {shape}", node.label())
+        };
+        format!(
+            "{:?}",
+            Dot::with_attr_getters(
+                &self.graph,
+                &[Config::EdgeNoLabel, Config::NodeNoLabel],
+                &edge_attributes,
+                &node_attributes,
+            )
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let nodes = self
+            .graph
+            .node_weights()
+            .enumerate()
+            .map(|(index, node)| {
+                let mut json = json!({
+                    "id": index,
+                    "entity": node.entity().to_string(),
+                });
+                let fields = match node {
+                    AttributeGraphNode::Attribute {
+                        name, kind, value, ..
+                    } => json!({
+                        "type": "attribute",
+                        "name": name,
+                        "kind": kind,
+                        "value": value.map(Number::to_f64),
+                    }),
+                    AttributeGraphNode::Modifier {
+                        name,
+                        ratio,
+                        delta,
+                        dynamic,
+                        active,
+                        conditional,
+                        contextual,
+                        ..
+                    } => json!({
+                        "type": "modifier",
+                        "name": name,
+                        "ratio": ratio.to_f64(),
+                        "delta": delta.to_f64(),
+                        "dynamic": dynamic,
+                        "active": active,
+                        "conditional": conditional,
+                        "contextual": contextual,
+                    }),
+                    AttributeGraphNode::Missing(_) => json!({ "type": "missing" }),
+                };
+                if let (Value::Object(json), Value::Object(fields)) = (&mut json, fields) {
+                    json.extend(fields);
+                }
+                json
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .graph
+            .edge_references()
+            .map(|edge| {
+                json!({
+                    "from": edge.source().index(),
+                    "to": edge.target().index(),
+                    "kind": edge.weight().name(),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "nodes": nodes, "edges": edges }).to_string()
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn export_attribute_graph(
+    attributes: Query<(Entity, &Attribute, Option<&AttributeValue>, Option<&Name>)>,
+    modifiers: Query<(
+        Entity,
+        &Modifier,
+        Option<&ModifierValue>,
+        Option<&DynamicModifier>,
+        Option<&Name>,
+        (
+            Has<InactiveModifier>,
+            Has<ModifierCondition>,
+            Has<ModifierContext>,
+        ),
+    )>,
+) -> AttributeGraphExport {
+    let mut graph = DiGraph::new();
+    let mut entity_node_map = EntityHashMap::<NodeIndex>::default();
+    let mut attributes = attributes.iter().collect::<Vec<_>>();
+    attributes.sort_by_key(|(entity, ..)| entity.index());
+    let mut modifiers = modifiers.iter().collect::<Vec<_>>();
+    modifiers.sort_by_key(|(entity, ..)| entity.index());
+
+    for (entity, attribute, value, name) in &attributes {
+        let kind = match attribute {
+            Attribute::Fixed => "Fixed",
+            Attribute::Plain(_) => "Plain",
+            Attribute::BasedOn(_) => "BasedOn",
+            Attribute::Merged(_) => "Merged",
+        };
+        let node = graph.add_node(AttributeGraphNode::Attribute {
+            entity: *entity,
+            name: name.map(|name| name.to_string()),
+            kind,
+            value: value.and_then(|value| **value),
+        });
+        entity_node_map.insert(*entity, node);
+    }
+    for (entity, _, value, dynamic_modifier, name, (inactive, conditional, contextual)) in
+        &modifiers
+    {
+        let value = value.copied().unwrap_or_default();
+        let node = graph.add_node(AttributeGraphNode::Modifier {
+            entity: *entity,
+            name: name.map(|name| name.to_string()),
+            ratio: value.ratio,
+            delta: value.delta,
+            dynamic: dynamic_modifier.is_some(),
+            active: !inactive,
+            conditional: *conditional,
+            contextual: *contextual,
+        });
+        entity_node_map.insert(*entity, node);
+    }
+
+    let mut node = |graph: &mut DiGraph<_, _>, entity: Entity| {
+        *entity_node_map
+            .entry(entity)
+            .or_insert_with(|| graph.add_node(AttributeGraphNode::Missing(entity)))
+    };
+    for (entity, attribute, ..) in &attributes {
+        let target = node(&mut graph, *entity);
+        match attribute {
+            Attribute::BasedOn(base_entity) => {
+                let source = node(&mut graph, *base_entity);
+                graph.add_edge(source, target, AttributeGraphEdge::BasedOn);
+            }
+            Attribute::Merged(dependency_entities) => {
+                let mut dependency_entities =
+                    dependency_entities.iter().copied().collect::<Vec<_>>();
+                dependency_entities.sort_by_key(|entity| entity.index());
+                for dependency_entity in dependency_entities {
+                    let source = node(&mut graph, dependency_entity);
+                    graph.add_edge(source, target, AttributeGraphEdge::Merged);
+                }
+            }
+            Attribute::Fixed | Attribute::Plain(_) => {}
+        }
+    }
+    for (entity, modifier, _, dynamic_modifier, ..) in &modifiers {
+        let modifier_node = node(&mut graph, *entity);
+        let target = node(&mut graph, modifier.0);
+        graph.add_edge(modifier_node, target, AttributeGraphEdge::Modifier);
+        if let Some(dynamic_modifier) = dynamic_modifier {
+            let source = node(&mut graph, dynamic_modifier.source);
+            graph.add_edge(source, modifier_node, AttributeGraphEdge::Source);
+        }
+    }
+    AttributeGraphExport { graph }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{AttributePlugin, Tag};
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_export_attribute_graph() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let base = world
+            .spawn((Attribute::Plain(Scalar::ZERO), Name::new("ATK \"Base\"")))
+            .id();
+        world.spawn(Modifier::new(base, Scalar::ZERO, Scalar::from_f64(100.0)));
+        let final_ = world.spawn(Attribute::BasedOn(base)).id();
+        let dynamic_modifier = world
+            .spawn(DynamicModifier::new_copy(
+                final_,
+                base,
+                Scalar::from_f64(50.0),
+                Scalar::ONE,
+                Scalar::ZERO,
+            ))
+            .id();
+        let conditional_modifier = world
+            .spawn((
+                Modifier::new(base, Scalar::ZERO, Scalar::from_f64(10.0)),
+                ModifierCondition::HasTag(final_, Tag("broken")),
+            ))
+            .id();
+        app.update();
+
+        let export = app
+            .world_mut()
+            .run_system_once(export_attribute_graph)
+            .unwrap();
+        let json = serde_json::from_str::<Value>(&export.to_json()).unwrap();
+        let modifier = |entity: String, ratio: f64, delta: f64, dynamic: bool, active: bool| {
+            json!({
+                "entity": entity,
+                "type": "modifier",
+                "name": null,
+                "ratio": ratio,
+                "delta": delta,
+                "dynamic": dynamic,
+                "active": active,
+                "conditional": !active,
+                "contextual": false,
+            })
+        };
+        let mut expected = json!({
+            "nodes": [
+                {
+                    "entity": base.to_string(),
+                    "type": "attribute",
+                    "name": "ATK \"Base\"",
+                    "kind": "Plain",
+                    "value": 100.0,
+                },
+                {
+                    "entity": final_.to_string(),
+                    "type": "attribute",
+                    "name": null,
+                    "kind": "BasedOn",
+                    "value": 100.0,
+                },
+                modifier(export.graph[NodeIndex::new(2)].entity().to_string(), 0.0, 100.0, false, true),
+                modifier(dynamic_modifier.to_string(), 1.0, 0.0, true, true),
+                modifier(conditional_modifier.to_string(), 0.0, 10.0, false, false),
+            ],
+            "edges": [
+                { "from": 0, "to": 1, "kind": "based_on" },
+                { "from": 2, "to": 0, "kind": "modifier" },
+                { "from": 3, "to": 1, "kind": "modifier" },
+                { "from": 0, "to": 3, "kind": "source" },
+                { "from": 4, "to": 0, "kind": "modifier" },
+            ],
+        });
+        for (id, node) in expected["nodes"]
+            .as_array_mut()
+            .unwrap()
+            .iter_mut()
+            .enumerate()
+        {
+            node["id"] = json!(id);
+        }
+        assert_eq!(json, expected);
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("label = \"ATK \\\"Base\\\"\\nPlain = 100\" shape = box]"));
+        assert!(dot.contains("0 -> 3 [ label = \"source\" style = dotted]"));
+        assert!(dot.contains("\\ninactive\" shape = ellipse]"));
+    }
+}
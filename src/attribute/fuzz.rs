@@ -0,0 +1,319 @@
+use crate::attribute::{
+    Attribute, AttributePlugin, AttributeValue, DefaultDependencyDespawnPolicy,
+    DependencyDespawnPolicy, DynamicModifier, DynamicModifierType, InactiveModifier, Modifier,
+    ModifierValue, Modifiers, Number, Scalar, validate_attribute_graph,
+};
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Plain(i8),
+    BasedOn(Index),
+    Merged(Vec<Index>),
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Spawn(Kind),
+    SpawnFixed(i8),
+    Replace(Index, Kind),
+    Despawn(Index, u8),
+    AddModifier(Index, i8, i8),
+    ReplaceModifier(Index, i8, i8),
+    AddDynamicModifier {
+        target: Index,
+        source: Index,
+        threshold: i8,
+        ratio: i8,
+        delta: i8,
+        modifier_type: u8,
+    },
+    RemoveModifier(Index),
+    Update,
+}
+
+fn kind() -> impl Strategy<Value = Kind> {
+    prop_oneof![
+        any::<i8>().prop_map(Kind::Plain),
+        any::<Index>().prop_map(Kind::BasedOn),
+        prop::collection::vec(any::<Index>(), 1..4).prop_map(Kind::Merged),
+    ]
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => kind().prop_map(Op::Spawn),
+        1 => any::<i8>().prop_map(Op::SpawnFixed),
+        2 => (any::<Index>(), kind()).prop_map(|(index, kind)| Op::Replace(index, kind)),
+        1 => (any::<Index>(), 0..3u8).prop_map(|(index, policy)| Op::Despawn(index, policy)),
+        3 => (any::<Index>(), any::<i8>(), any::<i8>())
+            .prop_map(|(index, ratio, delta)| Op::AddModifier(index, ratio, delta)),
+        1 => (any::<Index>(), any::<i8>(), any::<i8>())
+            .prop_map(|(index, ratio, delta)| Op::ReplaceModifier(index, ratio, delta)),
+        2 => (
+            any::<Index>(),
+            any::<Index>(),
+            any::<i8>(),
+            any::<i8>(),
+            any::<i8>(),
+            0..3u8,
+        )
+            .prop_map(|(target, source, threshold, ratio, delta, modifier_type)| {
+                Op::AddDynamicModifier {
+                    target,
+                    source,
+                    threshold,
+                    ratio,
+                    delta,
+                    modifier_type,
+                }
+            }),
+        1 => any::<Index>().prop_map(Op::RemoveModifier),
+        1 => Just(Op::Update),
+    ]
+}
+
+fn ratio(value: i8) -> Scalar {
+    Scalar::from_f64(value as f64 / 64.0)
+}
+
+fn delta(value: i8) -> Scalar {
+    Scalar::from_f64(value as f64)
+}
+
+fn pick<T: Copy>(items: &[T], index: &Index) -> Option<T> {
+    (!items.is_empty()).then(|| items[index.index(items.len())])
+}
+
+/// Mirrors the entities the operations can refer to.
+///
+/// Attributes only ever depend on attributes spawned before them, which keeps
+/// every generated graph acyclic.
+#[derive(Default)]
+struct Harness {
+    attributes: Vec<Entity>,
+    modifiers: Vec<Entity>,
+}
+
+impl Harness {
+    fn attribute(&self, kind: &Kind, dependencies: &[Entity]) -> Option<Attribute> {
+        match kind {
+            Kind::Plain(base) => Some(Attribute::Plain(delta(*base))),
+            Kind::BasedOn(index) => pick(dependencies, index).map(Attribute::BasedOn),
+            Kind::Merged(indices) => {
+                let merged = indices
+                    .iter()
+                    .filter_map(|index| pick(dependencies, index))
+                    .collect::<EntityHashSet>();
+                (!merged.is_empty()).then_some(Attribute::Merged(merged))
+            }
+        }
+    }
+
+    fn apply(&mut self, world: &mut World, op: &Op) {
+        match op {
+            Op::Spawn(kind) => {
+                if let Some(attribute) = self.attribute(kind, &self.attributes) {
+                    self.attributes.push(world.spawn(attribute).id());
+                }
+            }
+            Op::SpawnFixed(value) => {
+                let entity = world
+                    .spawn((Attribute::Fixed, AttributeValue::new(Some(delta(*value)))))
+                    .id();
+                self.attributes.push(entity);
+            }
+            Op::Replace(index, kind) => {
+                let Some(position) = pick(&(0..self.attributes.len()).collect::<Vec<_>>(), index)
+                else {
+                    return;
+                };
+                if let Some(attribute) = self.attribute(kind, &self.attributes[..position]) {
+                    world
+                        .entity_mut(self.attributes[position])
+                        .insert(attribute);
+                }
+            }
+            Op::Despawn(index, policy) => {
+                let Some(entity) = pick(&self.attributes, index) else {
+                    return;
+                };
+                world.resource_mut::<DefaultDependencyDespawnPolicy>().0 = match policy {
+                    0 => DependencyDespawnPolicy::Cascade,
+                    1 => DependencyDespawnPolicy::Zero,
+                    _ => DependencyDespawnPolicy::Freeze,
+                };
+                world.despawn(entity);
+                self.attributes
+                    .retain(|attribute| world.get_entity(*attribute).is_ok());
+                self.modifiers
+                    .retain(|modifier| world.get_entity(*modifier).is_ok());
+            }
+            Op::AddModifier(index, ratio_value, delta_value) => {
+                if let Some(target) = pick(&self.attributes, index) {
+                    let entity = world
+                        .spawn(Modifier::new(
+                            target,
+                            ratio(*ratio_value),
+                            delta(*delta_value),
+                        ))
+                        .id();
+                    self.modifiers.push(entity);
+                }
+            }
+            Op::ReplaceModifier(index, ratio_value, delta_value) => {
+                if let Some(entity) = pick(&self.modifiers, index)
+                    && !world.entity(entity).contains::<DynamicModifier>()
+                {
+                    world.entity_mut(entity).insert(ModifierValue {
+                        ratio: ratio(*ratio_value),
+                        delta: delta(*delta_value),
+                    });
+                }
+            }
+            Op::AddDynamicModifier {
+                target,
+                source,
+                threshold,
+                ratio: ratio_value,
+                delta: delta_value,
+                modifier_type,
+            } => {
+                let Some(position) = pick(&(1..self.attributes.len()).collect::<Vec<_>>(), target)
+                else {
+                    return;
+                };
+                let source = pick(&self.attributes[..position], source).unwrap();
+                let modifier_type = match modifier_type {
+                    0 => DynamicModifierType::Copy,
+                    1 => DynamicModifierType::Scale,
+                    _ => DynamicModifierType::ScaleWithoutThreshold,
+                };
+                let entity = world
+                    .spawn(DynamicModifier::new(
+                        self.attributes[position],
+                        source,
+                        delta(*threshold),
+                        ratio(*ratio_value) / Scalar::from_f64(64.0),
+                        delta(*delta_value) / Scalar::from_f64(64.0),
+                        modifier_type,
+                    ))
+                    .id();
+                self.modifiers.push(entity);
+            }
+            Op::RemoveModifier(index) => {
+                if let Some(entity) = pick(&self.modifiers, index) {
+                    world.despawn(entity);
+                    self.modifiers.retain(|modifier| *modifier != entity);
+                }
+            }
+            Op::Update => {}
+        }
+    }
+
+    fn check(&self, app: &mut App) -> Result<(), TestCaseError> {
+        app.update();
+        let world = app.world_mut();
+        let discrepancies = world.run_system_once(validate_attribute_graph).unwrap();
+        prop_assert!(discrepancies.is_empty(), "{:?}", discrepancies);
+        let mut reference = EntityHashMap::default();
+        for entity in &self.attributes {
+            let expected = reference_value(world, *entity, &mut reference);
+            let actual = world.get::<AttributeValue>(*entity).unwrap().0;
+            prop_assert!(actual.is_some(), "{:?} was left unevaluated", entity);
+            let actual = actual.unwrap();
+            let tolerance = 1e-3 * expected.to_f64().abs().max(1.0);
+            prop_assert!(
+                (actual - expected).to_f64().abs() <= tolerance,
+                "{:?} evaluated to {} but the reference is {}",
+                entity,
+                actual,
+                expected
+            );
+        }
+        Ok(())
+    }
+}
+
+fn reference_value(world: &World, entity: Entity, memo: &mut EntityHashMap<Scalar>) -> Scalar {
+    if let Some(value) = memo.get(&entity) {
+        return *value;
+    }
+    let merged_modifiers = world.get::<Modifiers>(entity).map(|modifiers| {
+        modifiers
+            .iter()
+            .map(|modifier| reference_modifier_value(world, modifier, memo))
+            .fold((Scalar::ZERO, Scalar::ZERO), |(ratio, delta), (r, d)| {
+                (ratio + r, delta + d)
+            })
+    });
+    let value = match world.get::<Attribute>(entity).unwrap() {
+        Attribute::Fixed => world.get::<AttributeValue>(entity).unwrap().0.unwrap(),
+        Attribute::Plain(base) => merged_modifiers
+            .map(|(ratio, delta)| *base * ratio + delta)
+            .unwrap_or(Scalar::ZERO),
+        Attribute::BasedOn(base_entity) => {
+            let base = reference_value(world, *base_entity, memo);
+            merged_modifiers
+                .map(|(ratio, delta)| base * ratio + delta)
+                .unwrap_or(Scalar::ZERO)
+        }
+        Attribute::Merged(dependency_entities) => dependency_entities
+            .iter()
+            .map(|dependency| reference_value(world, *dependency, memo))
+            .sum(),
+    };
+    memo.insert(entity, value);
+    value
+}
+
+fn reference_modifier_value(
+    world: &World,
+    modifier: Entity,
+    memo: &mut EntityHashMap<Scalar>,
+) -> (Scalar, Scalar) {
+    if world.entity(modifier).contains::<InactiveModifier>() {
+        return (Scalar::ZERO, Scalar::ZERO);
+    }
+    let Some(dynamic_modifier) = world.get::<DynamicModifier>(modifier) else {
+        let modifier_value = world.get::<ModifierValue>(modifier).unwrap();
+        return (modifier_value.ratio, modifier_value.delta);
+    };
+    let source_value = reference_value(world, dynamic_modifier.source, memo);
+    if source_value < dynamic_modifier.threshold {
+        return (Scalar::ZERO, Scalar::ZERO);
+    }
+    let scale = match dynamic_modifier.modifier_type {
+        DynamicModifierType::Copy => Scalar::ONE,
+        DynamicModifierType::Scale => source_value,
+        DynamicModifierType::ScaleWithoutThreshold => source_value - dynamic_modifier.threshold,
+    };
+    (
+        dynamic_modifier.ratio * scale,
+        dynamic_modifier.delta * scale,
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_random_operations_match_reference(ops in prop::collection::vec(op(), 1..48)) {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let mut harness = Harness::default();
+        for op in &ops {
+            harness.apply(app.world_mut(), op);
+            if matches!(op, Op::Update) {
+                harness.check(&mut app)?;
+            }
+        }
+        harness.check(&mut app)?;
+    }
+}
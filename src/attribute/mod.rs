@@ -1,37 +1,62 @@
+mod aura;
+mod condition;
+mod context;
+mod despawn;
+mod eager;
+mod export;
+#[cfg(test)]
+mod fuzz;
 mod modifier;
+mod number;
 mod plugin;
+mod rounding;
+mod source;
 mod tag;
+mod team;
+mod validate;
 mod zone;
 
+pub use aura::*;
+pub use condition::*;
+pub use context::*;
+pub use despawn::*;
+pub use eager::*;
+pub use export::*;
 pub use modifier::*;
+pub use number::*;
 pub use plugin::*;
+pub use rounding::*;
+pub use source::*;
 pub use tag::*;
+pub use team::*;
+pub use validate::*;
 pub use zone::*;
 
 use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy::ecs::error::CommandWithEntity;
 use bevy::ecs::lifecycle::HookContext;
 use bevy::ecs::relationship::RelationshipSourceCollection;
-use bevy::ecs::system::{QueryParamBuilder, SystemParam};
+use bevy::ecs::system::{ParamBuilder, QueryParamBuilder, SystemParam};
 use bevy::ecs::world::DeferredWorld;
 use bevy::prelude::*;
 use petgraph::algo::toposort;
 use petgraph::prelude::DiGraph;
 use petgraph::visit::GraphBase;
-use std::mem::replace;
 use std::collections::VecDeque;
+use std::mem::take;
 
 #[derive(Component, Deref, Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
 #[component(on_insert = attribute_value_on_insert)]
-pub struct AttributeValue(Option<f32>);
+pub struct AttributeValue(Option<Scalar>);
 
 impl AttributeValue {
-    pub fn new(value: Option<f32>) -> Self {
+    pub fn new(value: Option<Scalar>) -> Self {
         Self(value)
     }
 }
 
 fn attribute_value_on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
     if let Attribute::Fixed = world.get::<Attribute>(entity).unwrap() {
         assert!(world.get::<AttributeValue>(entity).is_some());
     }
@@ -57,15 +82,16 @@ fn attribute_value_on_insert(mut world: DeferredWorld, HookContext { entity, ..
 }
 
 #[derive(Component, Clone, Debug, PartialEq)]
-#[component(on_insert = attribute_on_insert)]
+#[component(on_insert = attribute_on_insert, on_replace = attribute_on_replace)]
 pub enum Attribute {
     Fixed,
-    Plain(f32),
+    Plain(Scalar),
     BasedOn(Entity),
     Merged(EntityHashSet),
 }
 
 fn attribute_on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_topology_generation(&mut world);
     let mut refresh_value = true;
     let mut dependencies = None;
     match world.get::<Attribute>(entity).unwrap() {
@@ -106,61 +132,104 @@ fn attribute_on_insert(mut world: DeferredWorld, HookContext { entity, .. }: Hoo
 }
 
 fn attribute_on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
-    let mut dependencies = None;
-    match world.get::<Attribute>(entity).unwrap() {
-        Attribute::BasedOn(base_entity) => {
-            dependencies = world
-                .get::<AttributeDependencies>(entity)
-                .cloned()
-                .map(|d| d.release(*base_entity));
+    bump_topology_generation(&mut world);
+    let released_entities = match world.get::<Attribute>(entity).unwrap() {
+        Attribute::BasedOn(base_entity) => vec![*base_entity],
+        Attribute::Merged(dependency_entities) => dependency_entities.iter().copied().collect(),
+        Attribute::Fixed => return,
+        Attribute::Plain(_) => return,
+    };
+    release_dependencies(&mut world, entity, released_entities);
+}
+
+pub(crate) fn release_dependencies(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    released_entities: impl IntoIterator<Item = Entity>,
+) {
+    let Some(mut dependencies) = world.get_mut::<AttributeDependencies>(entity) else {
+        return;
+    };
+    let mut detached_entities = Vec::new();
+    for released_entity in released_entities {
+        if let Some(count) = dependencies.0.get_mut(&released_entity) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                dependencies.0.remove(&released_entity);
+                detached_entities.push(released_entity);
+            }
         }
-        Attribute::Merged(dependency_entities) => {
-            if let Some(mut attribute_dependencies) =
-                world.get::<AttributeDependencies>(entity).cloned()
+    }
+    if dependencies.0.is_empty() {
+        let command = |mut entity: EntityWorldMut| {
+            if entity
+                .get::<AttributeDependencies>()
+                .is_some_and(|d| d.0.is_empty())
             {
-                for dependency_entity in dependency_entities {
-                    attribute_dependencies = attribute_dependencies.release(*dependency_entity);
-                }
-                dependencies = Some(attribute_dependencies);
+                entity.remove::<AttributeDependencies>();
             }
-        }
-        Attribute::Fixed => {}
-        Attribute::Plain(_) => {}
+        };
+        world.commands().queue_silenced(command.with_entity(entity));
     }
-    if let Some(dependencies) = dependencies {
-        world.commands().entity(entity).try_insert(dependencies);
+    for detached_entity in detached_entities {
+        detach_dependent(world, detached_entity, entity);
+    }
+}
+
+fn detach_dependent(
+    world: &mut DeferredWorld,
+    dependency_entity: Entity,
+    dependent_entity: Entity,
+) {
+    if let Ok(mut dependency_entity_mut) = world.get_entity_mut(dependency_entity)
+        && let Some(mut dependents) = dependency_entity_mut.get_mut::<AttributeDependents>()
+    {
+        dependents.0.remove(dependent_entity);
+        if dependents.0.is_empty() {
+            let command = |mut dependency_entity: EntityWorldMut| {
+                if dependency_entity
+                    .get::<AttributeDependents>()
+                    .is_some_and(|d| d.0.is_empty())
+                {
+                    dependency_entity.remove::<AttributeDependents>();
+                }
+            };
+            world
+                .commands()
+                .queue_silenced(command.with_entity(dependency_entity));
+        }
     }
 }
 
 #[derive(Component, Deref, Default, Clone, Debug, PartialEq, Eq)]
-#[component(on_replace = attribute_value_dependents_on_replace)]
+#[component(
+    on_despawn = attribute_dependents_on_despawn,
+    on_replace = attribute_value_dependents_on_replace
+)]
 pub struct AttributeDependents(pub EntityHashSet);
 
 fn attribute_value_dependents_on_replace(
     mut world: DeferredWorld,
     HookContext { entity, .. }: HookContext,
 ) {
-    let dependent_entities = replace(
-        &mut world.get_mut::<AttributeDependents>(entity).unwrap().0,
-        EntityHashSet::default(),
-    );
+    let dependent_entities = take(&mut world.get_mut::<AttributeDependents>(entity).unwrap().0);
     for dependent_entity in dependent_entities {
-        if let Ok(mut dependent_entity_mut) = world.get_entity_mut(dependent_entity) {
-            if let Some(mut dependency) = dependent_entity_mut.get_mut::<AttributeDependencies>() {
-                dependency.0.remove(&entity);
-                if dependency.0.is_empty() {
-                    let command = |mut dependent_entity: EntityWorldMut| {
-                        if dependent_entity
-                            .get::<AttributeDependencies>()
-                            .is_some_and(|d| d.0.is_empty())
-                        {
-                            dependent_entity.remove::<AttributeDependencies>();
-                        }
-                    };
-                    world
-                        .commands()
-                        .queue_silenced(command.with_entity(dependent_entity));
-                }
+        if let Ok(mut dependent_entity_mut) = world.get_entity_mut(dependent_entity)
+            && let Some(mut dependency) = dependent_entity_mut.get_mut::<AttributeDependencies>()
+        {
+            dependency.0.remove(&entity);
+            if dependency.0.is_empty() {
+                let command = |mut dependent_entity: EntityWorldMut| {
+                    if dependent_entity
+                        .get::<AttributeDependencies>()
+                        .is_some_and(|d| d.0.is_empty())
+                    {
+                        dependent_entity.remove::<AttributeDependencies>();
+                    }
+                };
+                world
+                    .commands()
+                    .queue_silenced(command.with_entity(dependent_entity));
             }
         }
     }
@@ -237,45 +306,41 @@ fn attribute_dependencies_on_replace(
     mut world: DeferredWorld,
     HookContext { entity, .. }: HookContext,
 ) {
-    let dependency_entities = replace(
-        &mut world.get_mut::<AttributeDependencies>(entity).unwrap().0,
-        EntityHashMap::default(),
-    );
-    for dependency_entity in dependency_entities
-        .iter()
-        .filter(|(_, c)| **c != 0)
-        .map(|(e, _)| *e)
-    {
-        if let Ok(mut dependency_entity_mut) = world.get_entity_mut(dependency_entity) {
-            if let Some(mut dependents) = dependency_entity_mut.get_mut::<AttributeDependents>() {
-                dependents.0.remove(entity);
-                if dependents.0.is_empty() {
-                    let command = |mut dependency_entity: EntityWorldMut| {
-                        if dependency_entity
-                            .get::<AttributeDependents>()
-                            .is_some_and(|d| d.0.is_empty())
-                        {
-                            dependency_entity.remove::<AttributeDependents>();
-                        }
-                    };
-                    world
-                        .commands()
-                        .queue_silenced(command.with_entity(dependency_entity));
-                }
-            }
-        }
+    let dependency_entities = take(&mut world.get_mut::<AttributeDependencies>(entity).unwrap().0);
+    for dependency_entity in dependency_entities.into_keys() {
+        detach_dependent(&mut world, dependency_entity, entity);
     }
 }
 
 #[derive(EntityEvent)]
 pub struct DependencyAttributeDirtyEvent(pub Entity);
 
+#[allow(clippy::type_complexity)]
 #[derive(SystemParam)]
 #[system_param(builder)]
 pub struct AttributeQueries<'w, 's> {
     pub attributes: Query<'w, 's, (&'static Attribute, Option<&'static Modifiers>)>,
     pub attribute_values: Query<'w, 's, &'static mut AttributeValue, With<Attribute>>,
-    pub modifier_values: Query<'w, 's, &'static ModifierValue>,
+    pub modifier_values: Query<
+        'w,
+        's,
+        (
+            &'static ModifierValue,
+            Has<InactiveModifier>,
+            Option<&'static ModifierContext>,
+            Option<&'static DynamicModifier>,
+        ),
+    >,
+    pub generation: Option<Res<'w, AttributeGeneration>>,
+    pub attribute_roundings: Query<
+        'w,
+        's,
+        (
+            Option<&'static AttributeRounding>,
+            Option<&'static AttributeType>,
+        ),
+        With<Attribute>,
+    >,
 }
 
 impl<'w, 's> AttributeQueries<'w, 's> {
@@ -284,17 +349,76 @@ impl<'w, 's> AttributeQueries<'w, 's> {
             attributes: QueryParamBuilder::new(|_| {}),
             attribute_values: QueryParamBuilder::new(|_| {}),
             modifier_values: QueryParamBuilder::new(|_| {}),
+            generation: ParamBuilder,
+            attribute_roundings: QueryParamBuilder::new(|_| {}),
         }
     }
+
+    pub fn rounding(&self, entity: Entity) -> AttributeRounding {
+        match self.attribute_roundings.get(entity) {
+            Ok((Some(rounding), _)) => *rounding,
+            Ok((None, Some(attribute_type))) => attribute_type.rounding(),
+            _ => AttributeRounding::default(),
+        }
+    }
+
+    pub(crate) fn stored_value(&self, entity: Entity) -> Scalar {
+        self.attribute_values
+            .get(entity)
+            .map_or(Scalar::ZERO, |value| value.0.unwrap())
+    }
+}
+
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttributeGeneration {
+    pub topology: u64,
+    pub values: u64,
+}
+
+pub(crate) fn bump_topology_generation(world: &mut DeferredWorld) {
+    if let Some(mut generation) = world.get_resource_mut::<AttributeGeneration>() {
+        generation.topology = generation.topology.wrapping_add(1);
+        generation.values = generation.values.wrapping_add(1);
+    }
 }
 
-#[derive(Default)]
+pub(crate) fn bump_values_generation(world: &mut DeferredWorld) {
+    if let Some(mut generation) = world.get_resource_mut::<AttributeGeneration>() {
+        generation.values = generation.values.wrapping_add(1);
+    }
+}
+
+/// What a contextual evaluation sees besides the world: the context, the
+/// conditional modifiers' current state and the values evaluated so far.
+#[derive(Clone, Copy)]
+pub(crate) struct ContextScope<'a> {
+    context: &'a EvaluationContext,
+    conditions: &'a EntityHashMap<bool>,
+    values: &'a EntityHashMap<Scalar>,
+}
+
+/// Evaluates attributes, keeping values and evaluation orders across fetches
+/// while an [`AttributeGeneration`] resource tracks world changes. Without
+/// the resource each fetch starts from an empty cache.
+#[derive(Resource, Default)]
 pub struct AttributeEvaluator {
-    cache: EntityHashMap<f32>,
+    cache: EntityHashMap<Scalar>,
+    generation: Option<AttributeGeneration>,
+    orders: EntityHashMap<Vec<Entity>>,
+    context_orders: EntityHashMap<Vec<Entity>>,
+    graph: DiGraph<Entity, ()>,
+    entity_node_map: EntityHashMap<<DiGraph<Entity, ()> as GraphBase>::NodeId>,
+    entity_queue: VecDeque<Entity>,
+    sorted_entities: Vec<Entity>,
 }
 
 impl AttributeEvaluator {
-    pub fn fetch_value(&mut self, queries: &mut AttributeQueries, entity: Entity) -> Option<f32> {
+    pub fn fetch_value(
+        &mut self,
+        queries: &mut AttributeQueries,
+        entity: Entity,
+    ) -> Option<Scalar> {
+        let tracked = self.synchronize(queries.generation.as_deref());
         if let Some(value) = self.cache.get(&entity) {
             return Some(*value);
         }
@@ -316,18 +440,15 @@ impl AttributeEvaluator {
             }
             Ok((Attribute::Plain(_), None)) => {
                 if queries.attribute_values.get(entity).unwrap().0.is_none() {
-                    *queries.attribute_values.get_mut(entity).unwrap() = AttributeValue(Some(0.0))
+                    *queries.attribute_values.get_mut(entity).unwrap() =
+                        AttributeValue(Some(Scalar::ZERO))
                 }
-                self.cache.insert(entity, 0.0);
-                return Some(0.0);
+                self.cache.insert(entity, Scalar::ZERO);
+                return Some(Scalar::ZERO);
             }
             Ok((Attribute::Plain(base), Some(modifiers))) => {
-                if let AttributeValue(Some(value)) = queries.attribute_values.get(entity).unwrap() {
-                    self.cache.insert(entity, *value);
-                    return Some(*value);
-                }
-                let (ratio, delta) = Self::merge_modifiers(queries, modifiers);
-                let value = base * ratio + delta;
+                let (ratio, delta) = Self::merge_modifiers(queries, modifiers, None);
+                let value = queries.rounding(entity).combat.apply(*base * ratio + delta);
                 *queries.attribute_values.get_mut(entity).unwrap() = AttributeValue(Some(value));
                 self.cache.insert(entity, value);
                 return Some(value);
@@ -337,114 +458,272 @@ impl AttributeEvaluator {
             }
             _ => {}
         }
-        let mut graph = DiGraph::<Entity, ()>::new();
-        let mut entity_node_map: EntityHashMap<<DiGraph<Entity, ()> as GraphBase>::NodeId> =
-            EntityHashMap::with_capacity(2);
-        let mut entity_queue = VecDeque::with_capacity(2);
-        entity_queue.push_back(entity);
-        entity_node_map.insert(entity, graph.add_node(entity));
-        while let Some(current_entity) = entity_queue.pop_front() {
-            if let AttributeValue(Some(value)) =
-                queries.attribute_values.get(current_entity).unwrap()
+        let mut sorted_entities = take(&mut self.sorted_entities);
+        sorted_entities.clear();
+        if !tracked {
+            self.sort_dependencies(queries, entity, true, false, &mut sorted_entities);
+        } else if let Some(order) = self.orders.get(&entity) {
+            sorted_entities.extend_from_slice(order);
+        } else {
+            self.sort_dependencies(queries, entity, false, false, &mut sorted_entities);
+            self.orders.insert(entity, sorted_entities.clone());
+        }
+        for &entity in &sorted_entities {
+            if self.cache.contains_key(&entity) {
+                continue;
+            }
+            match queries.attribute_values.get(entity) {
+                Ok(AttributeValue(Some(value))) => {
+                    self.cache.insert(entity, *value);
+                    continue;
+                }
+                Ok(AttributeValue(None)) => {}
+                Err(_) => continue,
+            }
+            let cache = &mut self.cache;
+            let value = Self::evaluate(queries, entity, None, |dependency_entity| {
+                *cache
+                    .entry(dependency_entity)
+                    .or_insert_with(|| queries.stored_value(dependency_entity))
+            });
+            *queries.attribute_values.get_mut(entity).unwrap() = AttributeValue(Some(value));
+            self.cache.insert(entity, value);
+        }
+        self.sorted_entities = sorted_entities;
+        self.cache.get(&entity).copied()
+    }
+
+    /// Drops cached values and orders made stale by world changes. Without an
+    /// [`AttributeGeneration`] resource nothing tells what changed, so every
+    /// fetch starts from an empty cache.
+    fn synchronize(&mut self, generation: Option<&AttributeGeneration>) -> bool {
+        let Some(generation) = generation else {
+            self.cache.clear();
+            self.orders.clear();
+            self.context_orders.clear();
+            self.generation = None;
+            return false;
+        };
+        if self.generation.map(|g| g.topology) != Some(generation.topology) {
+            self.orders.clear();
+            self.context_orders.clear();
+        }
+        if self.generation.map(|g| g.values) != Some(generation.values) {
+            self.cache.clear();
+        }
+        self.generation = Some(*generation);
+        true
+    }
+
+    /// Orders `entity` after everything it depends on. With `dynamic_sources`
+    /// the sources of [`DynamicModifier`]s count as dependencies of their
+    /// targets, unless that closes a cycle.
+    fn sort_dependencies(
+        &mut self,
+        queries: &AttributeQueries,
+        entity: Entity,
+        prune: bool,
+        dynamic_sources: bool,
+        sorted_entities: &mut Vec<Entity>,
+    ) {
+        self.graph.clear();
+        self.entity_node_map.clear();
+        self.entity_queue.clear();
+        self.entity_queue.push_back(entity);
+        self.entity_node_map
+            .insert(entity, self.graph.add_node(entity));
+        while let Some(current_entity) = self.entity_queue.pop_front() {
+            if prune
+                && let Ok(AttributeValue(Some(value))) =
+                    queries.attribute_values.get(current_entity)
             {
                 self.cache.insert(current_entity, *value);
                 continue;
             }
-            let current_id = *entity_node_map.get(&current_entity).unwrap();
-            match queries.attributes.get(current_entity) {
-                Ok((Attribute::BasedOn(base_entity), Some(_))) => {
-                    if self.cache.contains_key(base_entity) {
-                        continue;
-                    }
-                    let base_id = if let Some(id) = entity_node_map.get(base_entity) {
-                        *id
-                    } else {
-                        let id = graph.add_node(*base_entity);
-                        entity_node_map.insert(*base_entity, id);
-                        entity_queue.push_back(*base_entity);
-                        id
-                    };
-                    graph.update_edge(base_id, current_id, ());
-                }
+            let current_id = *self.entity_node_map.get(&current_entity).unwrap();
+            let mut dependency_entities = match queries.attributes.get(current_entity) {
+                Ok((Attribute::BasedOn(base_entity), Some(_))) => vec![*base_entity],
+                Ok((Attribute::BasedOn(base_entity), None)) if !prune => vec![*base_entity],
                 Ok((Attribute::Merged(dependency_entities), _)) => {
-                    for dependency_entity in dependency_entities {
-                        if self.cache.contains_key(dependency_entity) {
-                            continue;
-                        }
-                        let dependency_id = if let Some(id) = entity_node_map.get(dependency_entity)
-                        {
-                            *id
-                        } else {
-                            let id = graph.add_node(*dependency_entity);
-                            entity_node_map.insert(*dependency_entity, id);
-                            entity_queue.push_back(*dependency_entity);
-                            id
-                        };
-                        graph.update_edge(dependency_id, current_id, ());
-                    }
+                    dependency_entities.iter().copied().collect()
                 }
-                _ => {}
+                _ => Vec::new(),
             };
-        }
-        let sorted_entities = toposort(&graph, None)
-            .unwrap()
-            .into_iter()
-            .map(|i| *graph.node_weight(i).unwrap());
-        for entity in sorted_entities {
-            let (attribute, modifiers) = queries.attributes.get(entity).unwrap();
-            let merged_ratio = modifiers.map(|modifiers| Self::merge_modifiers(queries, modifiers));
-            let value = match attribute {
-                Attribute::Fixed => queries.attribute_values.get(entity).unwrap().0.unwrap(),
-                Attribute::Plain(base) => {
-                    if let Some((ratio, delta)) = merged_ratio {
-                        base * ratio + delta
-                    } else {
-                        0.0
-                    }
-                }
-                Attribute::BasedOn(base_entity) => {
-                    if let Some((ratio, delta)) = merged_ratio {
-                        let base = self.get_cache_or_fetch_attribute_value(queries, *base_entity);
-                        base * ratio + delta
-                    } else {
-                        0.0
-                    }
+            if dynamic_sources
+                && let Ok((_, Some(modifiers))) = queries.attributes.get(current_entity)
+            {
+                dependency_entities.extend(modifiers.iter().filter_map(|modifier| {
+                    Some(queries.modifier_values.get(modifier).ok()?.3?.source)
+                }));
+            }
+            for dependency_entity in dependency_entities {
+                if prune && self.cache.contains_key(&dependency_entity) {
+                    continue;
                 }
-                Attribute::Merged(dependency_entities) => dependency_entities
-                    .iter()
-                    .copied()
-                    .collect::<Vec<_>>()
-                    .into_iter()
-                    .map(|e| self.get_cache_or_fetch_attribute_value(queries, e))
-                    .sum(),
-            };
-            *queries.attribute_values.get_mut(entity).unwrap() = AttributeValue(Some(value));
-            self.cache.insert(entity, value);
+                let dependency_id = if let Some(id) = self.entity_node_map.get(&dependency_entity) {
+                    *id
+                } else {
+                    let id = self.graph.add_node(dependency_entity);
+                    self.entity_node_map.insert(dependency_entity, id);
+                    self.entity_queue.push_back(dependency_entity);
+                    id
+                };
+                self.graph.update_edge(dependency_id, current_id, ());
+            }
         }
-        self.cache.get(&entity).copied()
+        let order = toposort(&self.graph, None);
+        if order.is_err() && dynamic_sources {
+            return self.sort_dependencies(queries, entity, prune, false, sorted_entities);
+        }
+        sorted_entities.extend(
+            order
+                .unwrap()
+                .into_iter()
+                .map(|i| *self.graph.node_weight(i).unwrap()),
+        );
+    }
+
+    pub fn fetch_display_value(
+        &mut self,
+        queries: &mut AttributeQueries,
+        entity: Entity,
+    ) -> Option<Scalar> {
+        let value = self.fetch_value(queries, entity)?;
+        Some(queries.rounding(entity).display.apply(value))
     }
 
-    fn get_cache_or_fetch_attribute_value(
+    /// Evaluates `entity` with the modifiers that apply to `context`, checking
+    /// their [`ModifierCondition`]s now rather than relying on the last
+    /// [`sync_modifier_conditions`] and deriving [`DynamicModifier`]s from
+    /// their sources evaluated in the same context. Nothing is written back
+    /// for the contextual values, so cached panel values stay untouched.
+    pub fn fetch_value_in_context(
         &mut self,
         queries: &mut AttributeQueries,
+        condition_queries: &ModifierConditionQueries,
         entity: Entity,
-    ) -> f32 {
-        if let Some(v) = self.cache.get(&entity) {
-            *v
+        context: &EvaluationContext,
+    ) -> Option<Scalar> {
+        queries.attributes.get(entity).ok()?;
+        let tracked = self.synchronize(queries.generation.as_deref());
+        let mut sorted_entities = take(&mut self.sorted_entities);
+        sorted_entities.clear();
+        if let Some(order) = self.context_orders.get(&entity) {
+            sorted_entities.extend_from_slice(order);
         } else {
-            let v = queries.attribute_values.get(entity).unwrap().0.unwrap();
-            self.cache.insert(entity, v);
-            v
+            self.sort_dependencies(queries, entity, false, true, &mut sorted_entities);
+            if tracked {
+                self.context_orders.insert(entity, sorted_entities.clone());
+            }
+        }
+        let conditional_modifiers = sorted_entities
+            .iter()
+            .filter_map(|e| queries.attributes.get(e).ok()?.1)
+            .flat_map(|modifiers| modifiers.iter())
+            .filter(|e| condition_queries.conditions.contains(*e))
+            .collect::<Vec<_>>();
+        let mut conditions = EntityHashMap::with_capacity(conditional_modifiers.len());
+        for modifier in conditional_modifiers {
+            let condition = condition_queries.conditions.get(modifier).unwrap();
+            conditions.insert(
+                modifier,
+                condition.evaluate(condition_queries, queries, self),
+            );
+        }
+        let mut values = EntityHashMap::with_capacity(sorted_entities.len());
+        for &entity in &sorted_entities {
+            if queries.attributes.get(entity).is_err() {
+                continue;
+            }
+            let scope = ContextScope {
+                context,
+                conditions: &conditions,
+                values: &values,
+            };
+            let value = Self::evaluate(queries, entity, Some(scope), |dependency_entity| {
+                values
+                    .get(&dependency_entity)
+                    .copied()
+                    .unwrap_or_else(|| queries.stored_value(dependency_entity))
+            });
+            values.insert(entity, value);
         }
+        self.sorted_entities = sorted_entities;
+        values.get(&entity).copied()
     }
 
-    fn merge_modifiers(queries: &AttributeQueries, modifiers: &Modifiers) -> (f32, f32) {
-        println!("Merging {} modifiers", modifiers.len());
-        modifiers
+    pub(crate) fn evaluate(
+        queries: &AttributeQueries,
+        entity: Entity,
+        scope: Option<ContextScope>,
+        mut dependency_value: impl FnMut(Entity) -> Scalar,
+    ) -> Scalar {
+        let (attribute, modifiers) = queries.attributes.get(entity).unwrap();
+        let merged_ratio =
+            modifiers.map(|modifiers| Self::merge_modifiers(queries, modifiers, scope));
+        let value = match attribute {
+            Attribute::Fixed => return queries.attribute_values.get(entity).unwrap().0.unwrap(),
+            Attribute::Plain(base) => {
+                if let Some((ratio, delta)) = merged_ratio {
+                    *base * ratio + delta
+                } else {
+                    Scalar::ZERO
+                }
+            }
+            Attribute::BasedOn(base_entity) => {
+                if let Some((ratio, delta)) = merged_ratio {
+                    dependency_value(*base_entity) * ratio + delta
+                } else {
+                    Scalar::ZERO
+                }
+            }
+            Attribute::Merged(dependency_entities) => {
+                let mut values = dependency_entities
+                    .iter()
+                    .map(|e| dependency_value(*e))
+                    .collect::<Vec<_>>();
+                values.sort_by(Number::total_cmp);
+                values.into_iter().sum()
+            }
+        };
+        queries.rounding(entity).combat.apply(value)
+    }
+
+    fn merge_modifiers(
+        queries: &AttributeQueries,
+        modifiers: &Modifiers,
+        scope: Option<ContextScope>,
+    ) -> (Scalar, Scalar) {
+        let mut modifier_values = modifiers
             .iter()
-            .map(|e| queries.modifier_values.get(e).unwrap())
-            .fold((0.0, 0.0), |(ratio, delta), m| {
-                println!("Merging modifier: ratio {}, delta {}", m.ratio, m.delta);
+            .filter_map(|e| {
+                let (value, inactive, modifier_context, dynamic_modifier) =
+                    queries.modifier_values.get(e).unwrap();
+                let Some(scope) = scope else {
+                    return (!inactive && modifier_context.is_none()).then_some(*value);
+                };
+                let active = scope.conditions.get(&e).copied().unwrap_or(!inactive);
+                let value = dynamic_modifier
+                    .and_then(|dynamic_modifier| {
+                        let source_value = scope.values.get(&dynamic_modifier.source)?;
+                        let (ratio, delta) = dynamic_modifier.value(*source_value);
+                        Some(ModifierValue { ratio, delta })
+                    })
+                    .unwrap_or(*value);
+                (active
+                    && modifier_context
+                        .is_none_or(|modifier_context| modifier_context.applies_to(scope.context)))
+                .then_some(value)
+            })
+            .collect::<Vec<_>>();
+        modifier_values.sort_by(|a, b| {
+            a.ratio
+                .total_cmp(&b.ratio)
+                .then_with(|| a.delta.total_cmp(&b.delta))
+        });
+        modifier_values
+            .into_iter()
+            .fold((Scalar::ZERO, Scalar::ZERO), |(ratio, delta), m| {
                 (ratio + m.ratio, delta + m.delta)
             })
     }
@@ -453,7 +732,6 @@ impl AttributeEvaluator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bevy::prelude::*;
 
     #[test]
     fn test_attribute_evaluator() {
@@ -468,26 +746,29 @@ mod tests {
         });
 
         let attr_a = world
-            .spawn((Attribute::Fixed, AttributeValue(Some(42.0))))
+            .spawn((
+                Attribute::Fixed,
+                AttributeValue(Some(Scalar::from_f64(42.0))),
+            ))
             .id();
 
         let attr_b = world.spawn((Attribute::BasedOn(attr_a),)).id();
 
-        let attr_c = world.spawn(Attribute::Plain(100.0)).id();
+        let attr_c = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
 
         world.spawn((
             Modifier(attr_b),
             ModifierValue {
-                ratio: 0.5,
-                delta: 8.0,
+                ratio: Scalar::from_f64(0.5),
+                delta: Scalar::from_f64(8.0),
             },
         ));
 
         world.spawn((
             Modifier(attr_c),
             ModifierValue {
-                ratio: 0.7,
-                delta: -10.0,
+                ratio: Scalar::from_f64(0.7),
+                delta: Scalar::from_f64(-10.0),
             },
         ));
 
@@ -506,16 +787,139 @@ mod tests {
             let mut queries = state.get_mut(&mut world);
             let mut evaluator = AttributeEvaluator::default();
             let value_d = evaluator.fetch_value(&mut queries, attr_d).unwrap();
-            assert_eq!(value_d, (42.0 * 0.5 + 8.0) + (100.0 * 0.7 - 10.0));
+            assert_eq!(
+                value_d,
+                (Scalar::from_f64(42.0 * 0.5 + 8.0)) + (Scalar::from_f64(100.0 * 0.7 - 10.0))
+            );
         }
 
-        world.entity_mut(attr_a).insert(AttributeValue(Some(84.0)));
+        world
+            .entity_mut(attr_a)
+            .insert(AttributeValue(Some(Scalar::from_f64(84.0))));
 
         {
             let mut queries = state.get_mut(&mut world);
             let mut evaluator = AttributeEvaluator::default();
             let value_d = evaluator.fetch_value(&mut queries, attr_d).unwrap();
-            assert_eq!(value_d, (84.0 * 0.5 + 8.0) + (100.0 * 0.7 - 10.0));
+            assert_eq!(
+                value_d,
+                (Scalar::from_f64(84.0 * 0.5 + 8.0)) + (Scalar::from_f64(100.0 * 0.7 - 10.0))
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluation_order_independent_of_spawn_order() {
+        fn evaluate(order: &[usize]) -> (u64, u64) {
+            let values = [
+                (Scalar::from_f64(1.0e8), Scalar::from_f64(0.1)),
+                (Scalar::from_f64(0.1), Scalar::from_f64(1.0e8)),
+                (Scalar::from_f64(-1.0e8), Scalar::from_f64(0.3)),
+                (Scalar::from_f64(0.3), Scalar::from_f64(-1.0e8)),
+                (Scalar::from_f64(0.7), Scalar::from_f64(0.7)),
+            ];
+            let mut world = World::new();
+            let attr = world.spawn(Attribute::Plain(Scalar::from_f64(3.0))).id();
+            let mut members = EntityHashSet::new();
+            for &i in order {
+                let (ratio, delta) = values[i];
+                world.spawn(Modifier::new(attr, ratio, delta));
+                members.insert(
+                    world
+                        .spawn((Attribute::Fixed, AttributeValue(Some(delta))))
+                        .id(),
+                );
+            }
+            let merged = world.spawn(Attribute::Merged(members)).id();
+            let mut state = AttributeQueries::builder().build_state(&mut world);
+            let mut queries = state.get_mut(&mut world);
+            let mut evaluator = AttributeEvaluator::default();
+            (
+                Number::to_bits(evaluator.fetch_value(&mut queries, attr).unwrap()),
+                Number::to_bits(evaluator.fetch_value(&mut queries, merged).unwrap()),
+            )
         }
+
+        let expected = evaluate(&[0, 1, 2, 3, 4]);
+        assert_eq!(evaluate(&[4, 3, 2, 1, 0]), expected);
+        assert_eq!(evaluate(&[2, 0, 4, 1, 3]), expected);
+    }
+
+    #[test]
+    fn test_attribute_replace_moves_dependencies() {
+        let mut world = World::new();
+        let attr_a = world.spawn(Attribute::Plain(Scalar::ONE)).id();
+        let attr_b = world.spawn(Attribute::Plain(Scalar::from_f64(2.0))).id();
+        let attr_c = world.spawn(Attribute::BasedOn(attr_a)).id();
+        world.flush();
+
+        world.entity_mut(attr_c).insert(Attribute::BasedOn(attr_b));
+        world.flush();
+
+        assert!(world.get::<AttributeDependents>(attr_a).is_none());
+        assert!(
+            world
+                .get::<AttributeDependents>(attr_b)
+                .unwrap()
+                .contains(&attr_c)
+        );
+        let dependencies = world.get::<AttributeDependencies>(attr_c).unwrap();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies.get(&attr_b), Some(&1));
+
+        world
+            .entity_mut(attr_c)
+            .insert(Attribute::Plain(Scalar::ZERO));
+        world.flush();
+
+        assert!(world.get::<AttributeDependents>(attr_b).is_none());
+        assert!(world.get::<AttributeDependencies>(attr_c).is_none());
+    }
+
+    #[test]
+    fn test_persistent_evaluator_tracks_generations() {
+        let mut world = World::new();
+        world.init_resource::<AttributeGeneration>();
+        let base = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        let base_modifier = world
+            .spawn(Modifier::new(base, Scalar::ZERO, Scalar::from_f64(100.0)))
+            .id();
+        let delta = world.spawn(Attribute::BasedOn(base)).id();
+        world.spawn(Modifier::new(delta, Scalar::from_f64(0.5), Scalar::ZERO));
+        let final_ = world
+            .spawn(Attribute::Merged(EntityHashSet::from_iter([base, delta])))
+            .id();
+        world.flush();
+
+        let mut state = AttributeQueries::builder().build_state(&mut world);
+        let mut evaluator = AttributeEvaluator::default();
+        let mut fetch = |world: &mut World, evaluator: &mut AttributeEvaluator| {
+            let mut queries = state.get_mut(world);
+            evaluator.fetch_value(&mut queries, final_).unwrap()
+        };
+
+        assert_eq!(fetch(&mut world, &mut evaluator), Scalar::from_f64(150.0));
+        assert_eq!(fetch(&mut world, &mut evaluator), Scalar::from_f64(150.0));
+
+        world.entity_mut(base_modifier).insert(ModifierValue {
+            ratio: Scalar::ZERO,
+            delta: Scalar::from_f64(200.0),
+        });
+        world.flush();
+        assert_eq!(fetch(&mut world, &mut evaluator), Scalar::from_f64(300.0));
+
+        world
+            .entity_mut(delta)
+            .insert(Attribute::Plain(Scalar::from_f64(10.0)));
+        world.flush();
+        assert_eq!(fetch(&mut world, &mut evaluator), Scalar::from_f64(205.0));
+
+        world.remove_resource::<AttributeGeneration>();
+        world.entity_mut(base_modifier).insert(ModifierValue {
+            ratio: Scalar::ZERO,
+            delta: Scalar::from_f64(50.0),
+        });
+        world.flush();
+        assert_eq!(fetch(&mut world, &mut evaluator), Scalar::from_f64(55.0));
     }
 }
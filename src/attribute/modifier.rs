@@ -1,6 +1,7 @@
 use crate::attribute::{
-    AttributeDependencies, AttributeEvaluator, AttributeQueries, AttributeValue,
-    DependencyAttributeDirtyEvent,
+    Attribute, AttributeDependencies, AttributeEvaluator, AttributeQueries, AttributeValue,
+    DependencyAttributeDirtyEvent, Number, Scalar, bump_topology_generation,
+    bump_values_generation, release_dependencies,
 };
 use bevy::ecs::entity::EntityHashSet;
 use bevy::ecs::lifecycle::HookContext;
@@ -13,26 +14,26 @@ use bevy::prelude::*;
 #[component(on_insert = modifier_value_on_insert)]
 #[component(on_remove = modifier_value_on_remove)]
 pub struct ModifierValue {
-    pub ratio: f32,
-    pub delta: f32,
+    pub ratio: Scalar,
+    pub delta: Scalar,
 }
 
 fn modifier_value_on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
     let target_entity = world.get::<Modifier>(entity).unwrap().0;
-    if world.entity(target_entity).contains::<AttributeValue>() {
-        world
-            .commands()
-            .entity(target_entity)
-            .insert(AttributeValue::default());
-    }
+    invalidate_modifier_target(&mut world, target_entity);
 }
 
 fn modifier_value_on_remove(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
     let target_entity = world.get::<Modifier>(entity).unwrap().0;
-    if world
-        .get_entity(target_entity)
-        .is_ok_and(|e| e.contains::<AttributeValue>())
-    {
+    invalidate_modifier_target(&mut world, target_entity);
+}
+
+pub(super) fn invalidate_modifier_target(world: &mut DeferredWorld, target_entity: Entity) {
+    if world.get_entity(target_entity).is_ok_and(|e| {
+        e.contains::<AttributeValue>() && !matches!(e.get::<Attribute>(), Some(Attribute::Fixed))
+    }) {
         world
             .commands()
             .entity(target_entity)
@@ -50,7 +51,8 @@ pub struct Modifiers(EntityHashSet);
 pub struct Modifier(pub Entity);
 
 impl Modifier {
-    pub fn new(target: Entity, ratio: f32, delta: f32) -> impl Bundle {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(target: Entity, ratio: Scalar, delta: Scalar) -> impl Bundle {
         (Modifier(target), ModifierValue { ratio, delta })
     }
 }
@@ -61,19 +63,20 @@ impl Modifier {
 #[component(on_replace = dynamic_modifier_on_replace)]
 pub struct DynamicModifier {
     pub source: Entity,
-    pub threshold: f32,
-    pub ratio: f32,
-    pub delta: f32,
+    pub threshold: Scalar,
+    pub ratio: Scalar,
+    pub delta: Scalar,
     pub modifier_type: DynamicModifierType,
 }
 
 impl DynamicModifier {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         target: Entity,
         source: Entity,
-        threshold: f32,
-        ratio: f32,
-        delta: f32,
+        threshold: Scalar,
+        ratio: Scalar,
+        delta: Scalar,
         modifier_type: DynamicModifierType,
     ) -> impl Bundle {
         (
@@ -91,9 +94,9 @@ impl DynamicModifier {
     pub fn new_copy(
         target: Entity,
         source: Entity,
-        threshold: f32,
-        ratio: f32,
-        delta: f32,
+        threshold: Scalar,
+        ratio: Scalar,
+        delta: Scalar,
     ) -> impl Bundle {
         Self::new(
             target,
@@ -108,9 +111,9 @@ impl DynamicModifier {
     pub fn new_scale(
         target: Entity,
         source: Entity,
-        threshold: f32,
-        ratio: f32,
-        delta: f32,
+        threshold: Scalar,
+        ratio: Scalar,
+        delta: Scalar,
     ) -> impl Bundle {
         Self::new(
             target,
@@ -125,9 +128,9 @@ impl DynamicModifier {
     pub fn new_scale_without_threshold(
         target: Entity,
         source: Entity,
-        threshold: f32,
-        ratio: f32,
-        delta: f32,
+        threshold: Scalar,
+        ratio: Scalar,
+        delta: Scalar,
     ) -> impl Bundle {
         Self::new(
             target,
@@ -138,6 +141,20 @@ impl DynamicModifier {
             DynamicModifierType::ScaleWithoutThreshold,
         )
     }
+
+    pub fn value(&self, source_value: Scalar) -> (Scalar, Scalar) {
+        if source_value < self.threshold {
+            return (Scalar::ZERO, Scalar::ZERO);
+        }
+        match self.modifier_type {
+            DynamicModifierType::Copy => (self.ratio, self.delta),
+            DynamicModifierType::Scale => (self.ratio * source_value, self.delta * source_value),
+            DynamicModifierType::ScaleWithoutThreshold => {
+                let excess = source_value - self.threshold;
+                (self.ratio * excess, self.delta * excess)
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -148,6 +165,7 @@ pub enum DynamicModifierType {
 }
 
 fn dynamic_modifier_on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_topology_generation(&mut world);
     let dynamic_modifier = *world.get::<DynamicModifier>(entity).unwrap();
     let dependencies = world
         .get::<AttributeDependencies>(entity)
@@ -157,86 +175,76 @@ fn dynamic_modifier_on_insert(mut world: DeferredWorld, HookContext { entity, ..
         .commands()
         .entity(entity)
         .insert(dependencies.increase(dynamic_modifier.source));
-    let (ratio, delta) = unsafe {
-        let world_mut = world.as_unsafe_world_cell().world_mut();
-        world_mut.resource_scope(|world, mut state: Mut<DynamicModifierOnInsertCache>| {
-            let mut attribute_evaluator = AttributeEvaluator::default();
-            let mut attribute_queries = state.attribute_queries_state.get_mut(world);
-            calculate_dynamic_modifier_value(
-                &dynamic_modifier,
-                &mut attribute_queries,
-                &mut attribute_evaluator,
-            )
-        })
-    };
+    world.commands().queue(move |world: &mut World| {
+        let Some(dynamic_modifier) = world.get::<DynamicModifier>(entity).copied() else {
+            return;
+        };
+        let (ratio, delta) = fetch_value_with_hook_cache(world, dynamic_modifier.source)
+            .map_or((Scalar::ZERO, Scalar::ZERO), |source_value| {
+                dynamic_modifier.value(source_value)
+            });
+        world
+            .entity_mut(entity)
+            .insert(ModifierValue { ratio, delta });
+    });
+}
+
+fn fetch_value_with_hook_cache(world: &mut World, entity: Entity) -> Option<Scalar> {
     world
-        .commands()
-        .entity(entity)
-        .insert(ModifierValue { ratio, delta });
+        .try_resource_scope(|world, mut state: Mut<AttributeHookCache>| {
+            let AttributeHookCache {
+                attribute_queries_state,
+                attribute_evaluator,
+            } = &mut *state;
+            let mut attribute_queries = attribute_queries_state.get_mut(world);
+            attribute_evaluator.fetch_value(&mut attribute_queries, entity)
+        })
+        .flatten()
+}
+
+/// Evaluates `entity` from inside a hook, for values that are gone by the
+/// time queued commands run, such as those of an attribute being despawned.
+pub(super) fn fetch_value_in_hook(world: &mut DeferredWorld, entity: Entity) -> Option<Scalar> {
+    // SAFETY: `world` is borrowed exclusively for this call, so no other
+    // reference into the world is live while the `&mut World` exists. The
+    // evaluation only reads and writes component values and swaps the
+    // `AttributeHookCache` resource out and back in; it never spawns,
+    // despawns or moves entities between archetypes, so the structural change
+    // the hook is running in is left untouched.
+    let world = unsafe { world.as_unsafe_world_cell().world_mut() };
+    fetch_value_with_hook_cache(world, entity)
 }
 
 fn dynamic_modifier_on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_topology_generation(&mut world);
     let source_entity = world.get::<DynamicModifier>(entity).unwrap().source;
-    if let Some(dependencies) = world.get::<AttributeDependencies>(entity).cloned() {
-        world
-            .commands()
-            .entity(entity)
-            .try_insert(dependencies.release(source_entity));
-    }
+    release_dependencies(&mut world, entity, [source_entity]);
 }
 
 fn calculate_dynamic_modifier_value(
     dynamic_modifier: &DynamicModifier,
     attribute_queries: &mut AttributeQueries,
     attribute_evaluator: &mut AttributeEvaluator,
-) -> (f32, f32) {
-    let Some(source_value) =
-        attribute_evaluator.fetch_value(attribute_queries, dynamic_modifier.source)
-    else {
-        return (0.0, 0.0);
-    };
-    let above_threshold = source_value >= dynamic_modifier.threshold;
-    match dynamic_modifier.modifier_type {
-        DynamicModifierType::Copy => {
-            if above_threshold {
-                (dynamic_modifier.ratio, dynamic_modifier.delta)
-            } else {
-                (0.0, 0.0)
-            }
-        }
-        DynamicModifierType::Scale => {
-            if above_threshold {
-                (
-                    dynamic_modifier.ratio * source_value,
-                    dynamic_modifier.delta * source_value,
-                )
-            } else {
-                (0.0, 0.0)
-            }
-        }
-        DynamicModifierType::ScaleWithoutThreshold => {
-            if above_threshold {
-                let excess = source_value - dynamic_modifier.threshold;
-                (
-                    dynamic_modifier.ratio * excess,
-                    dynamic_modifier.delta * excess,
-                )
-            } else {
-                (0.0, 0.0)
-            }
-        }
-    }
+) -> (Scalar, Scalar) {
+    attribute_evaluator
+        .fetch_value(attribute_queries, dynamic_modifier.source)
+        .map_or((Scalar::ZERO, Scalar::ZERO), |source_value| {
+            dynamic_modifier.value(source_value)
+        })
 }
 
 pub fn dynamic_modifier_on_dependency_attribute_dirty_observer(
-    event: On<DependencyAttributeDirtyEvent, DynamicModifier>,
+    event: On<DependencyAttributeDirtyEvent>,
     dynamic_modifiers: Query<&DynamicModifier>,
     mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
     mut commands: Commands,
 ) {
-    let mut attribute_evaluator = AttributeEvaluator::default();
+    let Ok(dynamic_modifier) = dynamic_modifiers.get(event.event_target()) else {
+        return;
+    };
     let (ratio, delta) = calculate_dynamic_modifier_value(
-        dynamic_modifiers.get(event.event_target()).unwrap(),
+        dynamic_modifier,
         &mut attribute_queries,
         &mut attribute_evaluator,
     );
@@ -246,6 +254,62 @@ pub fn dynamic_modifier_on_dependency_attribute_dirty_observer(
 }
 
 #[derive(Resource, FromWorld)]
-pub(super) struct DynamicModifierOnInsertCache {
+pub(super) struct AttributeHookCache {
     attribute_queries_state: SystemState<AttributeQueries<'static, 'static>>,
+    attribute_evaluator: AttributeEvaluator,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin};
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_dynamic_modifier_chain_follows_source() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let root = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        let root_modifier = world
+            .spawn(Modifier::new(root, Scalar::ZERO, Scalar::from_f64(100.0)))
+            .id();
+        world.flush();
+        let mut tail = root;
+        for _ in 0..3 {
+            let next = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+            world.flush();
+            world.spawn(DynamicModifier::new_scale(
+                next,
+                tail,
+                Scalar::ZERO,
+                Scalar::ZERO,
+                Scalar::from_f64(0.5),
+            ));
+            world.flush();
+            tail = next;
+        }
+
+        let fetch_tail = move |mut queries: AttributeQueries,
+                               mut evaluator: ResMut<AttributeEvaluator>| {
+            evaluator.fetch_value(&mut queries, tail)
+        };
+        assert_eq!(
+            world.run_system_once(fetch_tail).unwrap(),
+            Some(Scalar::from_f64(12.5))
+        );
+
+        world.entity_mut(root_modifier).insert(ModifierValue {
+            ratio: Scalar::ZERO,
+            delta: Scalar::from_f64(200.0),
+        });
+        world.flush();
+        assert_eq!(
+            world.run_system_once(fetch_tail).unwrap(),
+            Some(Scalar::from_f64(25.0))
+        );
+    }
 }
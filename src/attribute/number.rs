@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+#[cfg(all(feature = "f64", feature = "decimal"))]
+compile_error!("the `f64` and `decimal` features are mutually exclusive");
+
+#[cfg(not(any(feature = "f64", feature = "decimal")))]
+pub type Scalar = f32;
+#[cfg(all(feature = "f64", not(feature = "decimal")))]
+pub type Scalar = f64;
+#[cfg(feature = "decimal")]
+pub type Scalar = Decimal;
+
+pub trait Number:
+    Copy
+    + Default
+    + Debug
+    + Display
+    + PartialEq
+    + PartialOrd
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + Sum
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// Relative rounding error of one operation, zero for exact
+    /// representations.
+    const EPSILON: Self;
+
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    /// The exact representation, equal only for bit-identical values.
+    fn to_bits(self) -> u64;
+    fn total_cmp(&self, other: &Self) -> Ordering;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+impl Number for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f32::EPSILON;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn to_bits(self) -> u64 {
+        f32::to_bits(self) as u64
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+
+    fn floor(self) -> Self {
+        f32::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        f32::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        f32::round(self)
+    }
+}
+
+impl Number for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f64::EPSILON;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+
+    fn floor(self) -> Self {
+        f64::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        f64::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        f64::round(self)
+    }
+}
+
+/// Fixed-point decimal with six fractional digits.
+///
+/// Multiplication and division truncate toward zero, matching the integer
+/// arithmetic the game uses for its own fixed-point values. Values range over
+/// about ±9.22e12 ([`Decimal::MIN`] to [`Decimal::MAX`]); arithmetic leaving
+/// that range, division by zero and converting a non-finite or out-of-range
+/// `f64` panic rather than wrap.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(i64);
+
+impl Decimal {
+    pub const SCALE: i64 = 1_000_000;
+    pub const MIN: Self = Self(i64::MIN);
+    pub const MAX: Self = Self(i64::MAX);
+
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+}
+
+impl Number for Decimal {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(Self::SCALE);
+    const EPSILON: Self = Self(0);
+
+    fn from_f64(value: f64) -> Self {
+        let raw = (value * Self::SCALE as f64).round();
+        assert!(
+            raw >= i64::MIN as f64 && raw < i64::MAX as f64,
+            "{value} is outside the decimal range"
+        );
+        Self(raw as i64)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+
+    fn to_bits(self) -> u64 {
+        self.0 as u64
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn floor(self) -> Self {
+        Self(self.0.div_euclid(Self::SCALE) * Self::SCALE)
+    }
+
+    fn ceil(self) -> Self {
+        -(-self).floor()
+    }
+
+    fn round(self) -> Self {
+        if self.0 < 0 {
+            -(-self).round()
+        } else {
+            Self(self.0 + Self::SCALE / 2).floor()
+        }
+    }
+}
+
+impl Add for Decimal {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match self.0.checked_add(rhs.0) {
+            Some(raw) => Self(raw),
+            None => panic!("decimal overflow: {self} + {rhs}"),
+        }
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        match self.0.checked_sub(rhs.0) {
+            Some(raw) => Self(raw),
+            None => panic!("decimal overflow: {self} - {rhs}"),
+        }
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match i64::try_from(self.0 as i128 * rhs.0 as i128 / Self::SCALE as i128) {
+            Ok(raw) => Self(raw),
+            Err(_) => panic!("decimal overflow: {self} * {rhs}"),
+        }
+    }
+}
+
+impl Div for Decimal {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.0 != 0, "decimal division by zero: {self} / 0");
+        match i64::try_from(self.0 as i128 * Self::SCALE as i128 / rhs.0 as i128) {
+            Ok(raw) => Self(raw),
+            Err(_) => panic!("decimal overflow: {self} / {rhs}"),
+        }
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self.0.checked_neg() {
+            Some(raw) => Self(raw),
+            None => panic!("decimal overflow: -{self}"),
+        }
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Debug for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let integer = self.0.unsigned_abs() / Self::SCALE as u64;
+        let fraction = self.0.unsigned_abs() % Self::SCALE as u64;
+        if fraction == 0 {
+            write!(f, "{sign}{integer}")
+        } else {
+            let fraction = format!("{fraction:06}");
+            write!(f, "{sign}{integer}.{}", fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_arithmetic() {
+        let a = Decimal::from_f64(1234.5);
+        let b = Decimal::from_f64(0.432);
+        assert_eq!(a + b, Decimal::from_raw(1_234_932_000));
+        assert_eq!(a - b, Decimal::from_raw(1_234_068_000));
+        assert_eq!(a * b, Decimal::from_f64(533.304));
+        assert_eq!(
+            Decimal::ONE / Decimal::from_f64(3.0),
+            Decimal::from_raw(333_333)
+        );
+        assert_eq!(-Decimal::from_raw(1), Decimal::from_raw(-1));
+        assert_eq!(
+            [a, b, -a].into_iter().sum::<Decimal>(),
+            Decimal::from_f64(0.432)
+        );
+        assert_eq!(Decimal::from_f64(-0.25).to_string(), "-0.25");
+        assert_eq!(Decimal::from_f64(3.0).to_string(), "3");
+        assert_eq!(Decimal::from_f64(-1.5).floor(), Decimal::from_f64(-2.0));
+        assert_eq!(Decimal::from_f64(-1.5).ceil(), Decimal::from_f64(-1.0));
+        assert_eq!(Decimal::from_f64(2.5).round(), Decimal::from_f64(3.0));
+        assert_eq!(Decimal::from_f64(-2.5).round(), Decimal::from_f64(-3.0));
+
+        let panics = |f: fn() -> Decimal| std::panic::catch_unwind(f).is_err();
+        assert!(panics(|| Decimal::from_f64(1e7) * Decimal::from_f64(1e7)));
+        assert!(panics(|| Decimal::MAX / Decimal::from_f64(0.5)));
+        assert!(panics(|| Decimal::ONE / Decimal::ZERO));
+        assert!(panics(|| Decimal::MAX + Decimal::from_raw(1)));
+        assert!(panics(|| -Decimal::MIN));
+        assert!(panics(|| Decimal::from_f64(1e13)));
+        assert!(panics(|| Decimal::from_f64(f64::NAN)));
+        assert_eq!(
+            Decimal::from_f64(3e6) * Decimal::from_f64(3e6),
+            Decimal::from_f64(9e12)
+        );
+    }
+}
@@ -1,12 +1,91 @@
-use crate::attribute::dynamic_modifier_on_dependency_attribute_dirty_observer;
-use crate::attribute::modifier::DynamicModifierOnInsertCache;
+use crate::attribute::modifier::AttributeHookCache;
+use crate::attribute::{
+    AttributeAggregate, AttributeComponentField, AttributeEvaluator, AttributeGeneration,
+    BaseZoneAttribute, DefaultDependencyDespawnPolicy, DeltaZoneAttribute, ExtraZoneAttribute,
+    FinalZoneAttribute, SafeZoneAttribute, dynamic_modifier_on_dependency_attribute_dirty_observer,
+    evaluate_attributes, sync_aggregate_attributes, sync_aura_modifiers, sync_component_attributes,
+    sync_modifier_conditions,
+};
+#[cfg(debug_assertions)]
+use crate::attribute::{report_attribute_graph_discrepancies, validate_attribute_graph};
 use bevy::prelude::*;
+use std::marker::PhantomData;
 
 pub struct AttributePlugin;
 
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttributeSystems {
+    Sync,
+    Evaluate,
+}
+
 impl Plugin for AttributePlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(dynamic_modifier_on_dependency_attribute_dirty_observer)
-            .init_resource::<DynamicModifierOnInsertCache>();
+            .init_resource::<AttributeGeneration>()
+            .init_resource::<DefaultDependencyDespawnPolicy>()
+            .init_resource::<AttributeEvaluator>()
+            .init_resource::<AttributeHookCache>()
+            .configure_sets(
+                PostUpdate,
+                AttributeSystems::Sync.before(AttributeSystems::Evaluate),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    sync_aura_modifiers::<BaseZoneAttribute>,
+                    sync_aura_modifiers::<DeltaZoneAttribute>,
+                    sync_aura_modifiers::<ExtraZoneAttribute>,
+                    sync_aura_modifiers::<SafeZoneAttribute>,
+                    sync_aura_modifiers::<FinalZoneAttribute>,
+                    sync_modifier_conditions,
+                )
+                    .in_set(AttributeSystems::Sync),
+            )
+            .add_systems(
+                PostUpdate,
+                evaluate_attributes.in_set(AttributeSystems::Evaluate),
+            );
+        #[cfg(debug_assertions)]
+        app.add_systems(
+            PostUpdate,
+            validate_attribute_graph
+                .pipe(report_attribute_graph_discrepancies)
+                .after(AttributeSystems::Evaluate),
+        );
+    }
+}
+
+pub struct AggregateAttributePlugin<A: AttributeAggregate>(PhantomData<A>);
+
+impl<A: AttributeAggregate> Default for AggregateAttributePlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: AttributeAggregate> Plugin for AggregateAttributePlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            sync_aggregate_attributes::<A>.in_set(AttributeSystems::Sync),
+        );
+    }
+}
+
+pub struct ComponentAttributePlugin<F: AttributeComponentField>(PhantomData<F>);
+
+impl<F: AttributeComponentField> Default for ComponentAttributePlugin<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: AttributeComponentField> Plugin for ComponentAttributePlugin<F> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            sync_component_attributes::<F>.in_set(AttributeSystems::Sync),
+        );
     }
 }
@@ -0,0 +1,191 @@
+use crate::attribute::modifier::invalidate_modifier_target;
+use crate::attribute::{Number, Scalar, bump_values_generation};
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+/// Positive step of a [`Rounding`].
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct RoundingStep(Scalar);
+
+impl RoundingStep {
+    /// Returns `None` unless `step` is positive.
+    pub fn new(step: Scalar) -> Option<Self> {
+        (step > Scalar::ZERO).then_some(Self(step))
+    }
+
+    pub fn get(self) -> Scalar {
+        self.0
+    }
+
+    /// Number of steps in `value`. A float quotient within a few ulps of an
+    /// integer is snapped to it, so `0.3` is 3000 steps of `0.0001` rather
+    /// than 2999.9998.
+    fn quotient(self, value: Scalar) -> Scalar {
+        let quotient = value / self.0;
+        let nearest = Number::round(quotient);
+        let abs = |value: Scalar| if value < Scalar::ZERO { -value } else { value };
+        let magnitude = if abs(quotient) > Scalar::ONE {
+            abs(quotient)
+        } else {
+            Scalar::ONE
+        };
+        if abs(quotient - nearest) <= Scalar::from_f64(8.0) * Scalar::EPSILON * magnitude {
+            nearest
+        } else {
+            quotient
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Rounding {
+    #[default]
+    Exact,
+    Floor(RoundingStep),
+    Ceil(RoundingStep),
+    Round(RoundingStep),
+}
+
+fn positive_step(step: Scalar) -> RoundingStep {
+    RoundingStep::new(step).unwrap_or_else(|| panic!("rounding step must be positive, got {step}"))
+}
+
+impl Rounding {
+    /// Panics unless `step` is positive.
+    pub fn floor(step: Scalar) -> Self {
+        Rounding::Floor(positive_step(step))
+    }
+
+    /// Panics unless `step` is positive.
+    pub fn ceil(step: Scalar) -> Self {
+        Rounding::Ceil(positive_step(step))
+    }
+
+    /// Panics unless `step` is positive.
+    pub fn round(step: Scalar) -> Self {
+        Rounding::Round(positive_step(step))
+    }
+
+    pub fn apply(self, value: Scalar) -> Scalar {
+        match self {
+            Rounding::Exact => value,
+            Rounding::Floor(step) => Number::floor(step.quotient(value)) * step.get(),
+            Rounding::Ceil(step) => Number::ceil(step.quotient(value)) * step.get(),
+            Rounding::Round(step) => Number::round(step.quotient(value)) * step.get(),
+        }
+    }
+}
+
+/// Rounding applied to an attribute at the two evaluation stages.
+///
+/// `combat` is applied to every value the evaluator computes before it is
+/// stored in [`AttributeValue`](crate::attribute::AttributeValue), so every
+/// downstream attribute and modifier sees the rounded value. `display` is
+/// applied on top of the combat value by
+/// [`AttributeEvaluator::fetch_display_value`](crate::attribute::AttributeEvaluator::fetch_display_value)
+/// only. `Fixed` attributes are stored exactly as provided.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[component(immutable)]
+#[component(on_insert = attribute_rounding_on_change)]
+#[component(on_remove = attribute_rounding_on_change)]
+pub struct AttributeRounding {
+    pub combat: Rounding,
+    pub display: Rounding,
+}
+
+fn attribute_rounding_on_change(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    bump_values_generation(&mut world);
+    invalidate_modifier_target(&mut world, entity);
+}
+
+impl AttributeRounding {
+    pub fn new(combat: Rounding, display: Rounding) -> Self {
+        Self { combat, display }
+    }
+
+    pub fn display(display: Rounding) -> Self {
+        Self {
+            combat: Rounding::Exact,
+            display,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::*;
+
+    #[test]
+    fn test_rounding_stages() {
+        let mut world = World::new();
+        world.init_resource::<AttributeGeneration>();
+        let attack = world
+            .spawn((Attribute::Plain(Scalar::ZERO), AttributeType::Attack))
+            .id();
+        world.spawn(Modifier::new(
+            attack,
+            Scalar::ZERO,
+            Scalar::from_f64(1234.56),
+        ));
+        let critical_chance = world
+            .spawn((
+                Attribute::Plain(Scalar::ZERO),
+                AttributeType::CriticalChance,
+                AttributeRounding::new(
+                    Rounding::floor(Scalar::from_f64(0.0001)),
+                    Rounding::round(Scalar::from_f64(0.001)),
+                ),
+            ))
+            .id();
+        world.spawn(Modifier::new(
+            critical_chance,
+            Scalar::ZERO,
+            Scalar::from_f64(0.12349),
+        ));
+
+        let mut state = AttributeQueries::builder().build_state(&mut world);
+        let mut queries = state.get_mut(&mut world);
+        let mut evaluator = AttributeEvaluator::default();
+
+        assert_eq!(
+            evaluator.fetch_value(&mut queries, attack),
+            Some(Scalar::from_f64(1234.56))
+        );
+        assert_eq!(
+            evaluator.fetch_display_value(&mut queries, attack),
+            Some(Scalar::from_f64(1234.0))
+        );
+        let combat = evaluator
+            .fetch_value(&mut queries, critical_chance)
+            .unwrap();
+        assert!((combat - Scalar::from_f64(0.1234)).to_f64().abs() < 1e-6);
+        let display = evaluator
+            .fetch_display_value(&mut queries, critical_chance)
+            .unwrap();
+        assert!((display - Scalar::from_f64(0.123)).to_f64().abs() < 1e-6);
+
+        // Swapping the rounding re-evaluates the stored value.
+        world
+            .entity_mut(critical_chance)
+            .insert(AttributeRounding::new(
+                Rounding::round(Scalar::from_f64(0.01)),
+                Rounding::Exact,
+            ));
+        world.flush();
+        let mut queries = state.get_mut(&mut world);
+        let combat = evaluator
+            .fetch_value(&mut queries, critical_chance)
+            .unwrap();
+        assert!((combat - Scalar::from_f64(0.12)).to_f64().abs() < 1e-6);
+
+        let floor = Rounding::floor(Scalar::from_f64(0.0001));
+        for value in [0.3, 0.7, 1.1, 0.1234] {
+            let rounded = floor.apply(Scalar::from_f64(value));
+            assert!((rounded - Scalar::from_f64(value)).to_f64().abs() < 1e-6);
+        }
+        assert_eq!(RoundingStep::new(Scalar::ZERO), None);
+        assert!(std::panic::catch_unwind(|| Rounding::round(Scalar::ZERO)).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::attribute::{Attribute, AttributeValue, Number, Scalar};
+use bevy::ecs::query::{QueryFilter, QueryItem, ReadOnlyQueryData};
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Describes the entities an [`AggregateAttribute`] counts and what each one
+/// contributes.
+pub trait AttributeAggregate: Send + Sync + 'static {
+    type Data: ReadOnlyQueryData;
+    type Filter: QueryFilter;
+
+    fn in_scope(_scope: Entity, _item: &QueryItem<Self::Data>) -> bool {
+        true
+    }
+
+    fn value(_item: &QueryItem<Self::Data>) -> Scalar {
+        Scalar::ONE
+    }
+}
+
+/// A `Fixed` attribute whose value is the sum of [`AttributeAggregate::value`]
+/// over every matching entity, optionally restricted to a scope entity.
+#[derive(Component)]
+#[require(
+    Attribute = Attribute::Fixed,
+    AttributeValue = AttributeValue::new(Some(Scalar::ZERO))
+)]
+pub struct AggregateAttribute<A: AttributeAggregate> {
+    pub scope: Option<Entity>,
+    pub aggregate: PhantomData<A>,
+}
+
+impl<A: AttributeAggregate> AggregateAttribute<A> {
+    pub fn new(scope: Option<Entity>) -> Self {
+        Self {
+            scope,
+            aggregate: PhantomData,
+        }
+    }
+}
+
+pub fn sync_aggregate_attributes<A: AttributeAggregate>(
+    aggregate_attributes: Query<(Entity, &AggregateAttribute<A>, &AttributeValue)>,
+    items: Query<A::Data, A::Filter>,
+    mut commands: Commands,
+) {
+    for (entity, aggregate_attribute, value) in &aggregate_attributes {
+        let total = items
+            .iter()
+            .filter(|item| {
+                aggregate_attribute
+                    .scope
+                    .is_none_or(|scope| A::in_scope(scope, item))
+            })
+            .map(|item| A::value(&item))
+            .sum::<Scalar>();
+        if **value != Some(total) {
+            commands
+                .entity(entity)
+                .insert(AttributeValue::new(Some(total)));
+        }
+    }
+}
+
+/// Reads a value out of a component so it can back a [`ComponentAttribute`].
+pub trait AttributeComponentField: Send + Sync + 'static {
+    type Source: Component;
+
+    fn value(source: &Self::Source) -> Scalar;
+}
+
+/// A `Fixed` attribute mirroring a field of a component on `source`, such as
+/// current HP or energy.
+#[derive(Component)]
+#[require(
+    Attribute = Attribute::Fixed,
+    AttributeValue = AttributeValue::new(Some(Scalar::ZERO))
+)]
+pub struct ComponentAttribute<F: AttributeComponentField> {
+    pub source: Entity,
+    pub field: PhantomData<F>,
+}
+
+impl<F: AttributeComponentField> ComponentAttribute<F> {
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source,
+            field: PhantomData,
+        }
+    }
+}
+
+pub fn sync_component_attributes<F: AttributeComponentField>(
+    component_attributes: Query<(Entity, Ref<ComponentAttribute<F>>, &AttributeValue)>,
+    sources: Query<Ref<F::Source>>,
+    mut commands: Commands,
+) {
+    for (entity, component_attribute, value) in &component_attributes {
+        let source_value = match sources.get(component_attribute.source) {
+            Ok(source) if source.is_changed() || component_attribute.is_changed() => {
+                F::value(&source)
+            }
+            Ok(_) => continue,
+            Err(_) => Scalar::ZERO,
+        };
+        if **value != Some(source_value) {
+            commands
+                .entity(entity)
+                .insert(AttributeValue::new(Some(source_value)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{
+        AggregateAttributePlugin, AttributePlugin, ComponentAttributePlugin, DynamicModifier,
+    };
+
+    #[derive(Component)]
+    struct Debuff {
+        target: Entity,
+        stacks: u32,
+    }
+
+    struct DebuffCount;
+
+    impl AttributeAggregate for DebuffCount {
+        type Data = &'static Debuff;
+        type Filter = ();
+
+        fn in_scope(scope: Entity, item: &&Debuff) -> bool {
+            item.target == scope
+        }
+    }
+
+    struct DebuffStacks;
+
+    impl AttributeAggregate for DebuffStacks {
+        type Data = &'static Debuff;
+        type Filter = ();
+
+        fn value(item: &&Debuff) -> Scalar {
+            Scalar::from_f64(item.stacks as f64)
+        }
+    }
+
+    #[test]
+    fn test_aggregate_attribute_tracks_matching_entities() {
+        let mut app = App::new();
+        app.add_plugins((
+            AttributePlugin,
+            AggregateAttributePlugin::<DebuffCount>::default(),
+            AggregateAttributePlugin::<DebuffStacks>::default(),
+        ));
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let enemy = world.spawn_empty().id();
+        let other_enemy = world.spawn_empty().id();
+        let debuff_count = world
+            .spawn(AggregateAttribute::<DebuffCount>::new(Some(enemy)))
+            .id();
+        let debuff_stacks = world
+            .spawn(AggregateAttribute::<DebuffStacks>::new(None))
+            .id();
+        let damage = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.spawn(DynamicModifier::new_scale(
+            damage,
+            debuff_count,
+            Scalar::ZERO,
+            Scalar::ZERO,
+            Scalar::from_f64(10.0),
+        ));
+        let first = world
+            .spawn(Debuff {
+                target: enemy,
+                stacks: 2,
+            })
+            .id();
+        world.spawn(Debuff {
+            target: enemy,
+            stacks: 1,
+        });
+        world.spawn(Debuff {
+            target: other_enemy,
+            stacks: 3,
+        });
+        app.update();
+
+        let value = |app: &App, entity| app.world().get::<AttributeValue>(entity).unwrap().0;
+        assert_eq!(value(&app, debuff_count), Some(Scalar::from_f64(2.0)));
+        assert_eq!(value(&app, debuff_stacks), Some(Scalar::from_f64(6.0)));
+        assert_eq!(value(&app, damage), Some(Scalar::from_f64(20.0)));
+
+        app.world_mut().despawn(first);
+        app.update();
+        assert_eq!(value(&app, debuff_count), Some(Scalar::ONE));
+        assert_eq!(value(&app, debuff_stacks), Some(Scalar::from_f64(4.0)));
+        assert_eq!(value(&app, damage), Some(Scalar::from_f64(10.0)));
+    }
+
+    #[derive(Component)]
+    struct Health {
+        current: Scalar,
+        max: Scalar,
+    }
+
+    struct HealthRatio;
+
+    impl AttributeComponentField for HealthRatio {
+        type Source = Health;
+
+        fn value(source: &Health) -> Scalar {
+            source.current / source.max
+        }
+    }
+
+    #[test]
+    fn test_component_attribute_follows_component() {
+        let mut app = App::new();
+        app.add_plugins((
+            AttributePlugin,
+            ComponentAttributePlugin::<HealthRatio>::default(),
+        ));
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let character = world
+            .spawn(Health {
+                current: Scalar::from_f64(1000.0),
+                max: Scalar::from_f64(1000.0),
+            })
+            .id();
+        let health_ratio = world
+            .spawn(ComponentAttribute::<HealthRatio>::new(character))
+            .id();
+        let attack = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn(DynamicModifier::new_copy(
+            attack,
+            health_ratio,
+            Scalar::from_f64(0.5),
+            Scalar::from_f64(1.25),
+            Scalar::ZERO,
+        ));
+        app.update();
+
+        let value = |app: &App, entity| app.world().get::<AttributeValue>(entity).unwrap().0;
+        assert_eq!(value(&app, health_ratio), Some(Scalar::ONE));
+        assert_eq!(value(&app, attack), Some(Scalar::from_f64(125.0)));
+
+        app.world_mut()
+            .get_mut::<Health>(character)
+            .unwrap()
+            .current = Scalar::from_f64(400.0);
+        app.update();
+        assert_eq!(value(&app, health_ratio), Some(Scalar::from_f64(0.4)));
+        assert_eq!(value(&app, attack), Some(Scalar::ZERO));
+
+        app.world_mut().entity_mut(character).remove::<Health>();
+        app.update();
+        assert_eq!(value(&app, health_ratio), Some(Scalar::ZERO));
+    }
+}
@@ -1,3 +1,11 @@
+use crate::attribute::{AttributeRounding, Number, Rounding, Scalar};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[reflect(Component)]
+#[reflect(PartialEq)]
+#[component(immutable)]
 pub enum AttributeType {
     MaxHP,
     Attack,
@@ -11,6 +19,7 @@ pub enum AttributeType {
     MaxSP,
     SpecialMaxSP,
     SPRegen,
+    SkillPointCap,
     StatusProbability,
     StatusResistance,
     PhysicalDamage,
@@ -27,4 +36,46 @@ pub enum AttributeType {
     QuantumResistance,
     ImaginaryDamage,
     ImaginaryResistance,
+    DamageBoost,
+    Weaken,
+    DefenseIgnore,
+    ResistancePenetration,
+    Vulnerability,
+    DamageMitigation,
+    WeaknessBreakEfficiency,
+}
+
+impl AttributeType {
+    pub fn is_flat(&self) -> bool {
+        matches!(
+            self,
+            AttributeType::MaxHP
+                | AttributeType::Attack
+                | AttributeType::Defense
+                | AttributeType::Speed
+                | AttributeType::MaxSP
+                | AttributeType::SpecialMaxSP
+                | AttributeType::SkillPointCap
+        )
+    }
+
+    pub fn rounding(&self) -> AttributeRounding {
+        if self.is_flat() {
+            AttributeRounding::display(Rounding::floor(Scalar::ONE))
+        } else {
+            AttributeRounding::display(Rounding::round(Scalar::from_f64(0.001)))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag(pub &'static str);
+
+#[derive(Component, Deref, DerefMut, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Tags(pub HashSet<Tag>);
+
+impl Tags {
+    pub fn new(tags: impl IntoIterator<Item = Tag>) -> Self {
+        Self(tags.into_iter().collect())
+    }
 }
@@ -0,0 +1,23 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[relationship(relationship_target = OwnedAttributes)]
+#[component(immutable)]
+pub struct AttributeOf(pub Entity);
+
+#[derive(Component, Deref, Default, Clone, Debug, PartialEq, Eq)]
+#[relationship_target(relationship = AttributeOf, linked_spawn)]
+pub struct OwnedAttributes(EntityHashSet);
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[relationship(relationship_target = TeamMembers)]
+#[component(immutable)]
+pub struct TeamMember(pub Entity);
+
+#[derive(Component, Deref, Default, Clone, Debug, PartialEq, Eq)]
+#[relationship_target(relationship = TeamMember)]
+pub struct TeamMembers(EntityHashSet);
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Defeated;
@@ -0,0 +1,249 @@
+use crate::attribute::{
+    Attribute, AttributeDependencies, AttributeDependents, DefaultDependencyDespawnPolicy,
+    DependencyDespawnPolicy, DynamicModifier,
+};
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AttributeGraphDiscrepancy {
+    DanglingDependency {
+        entity: Entity,
+        dependency: Entity,
+    },
+    DependencyCountMismatch {
+        entity: Entity,
+        dependency: Entity,
+        expected: usize,
+        actual: usize,
+    },
+    MissingDependent {
+        dependency: Entity,
+        dependent: Entity,
+    },
+    StaleDependent {
+        dependency: Entity,
+        dependent: Entity,
+    },
+    EmptyDependencies(Entity),
+    EmptyDependents(Entity),
+}
+
+/// Cross-checks [`AttributeDependencies`] and [`AttributeDependents`] against
+/// the sources declared by [`Attribute`] and [`DynamicModifier`].
+///
+/// Run it after commands have been applied: the hooks finish their
+/// bookkeeping through queued commands, so the graph is only consistent
+/// once those are flushed. Dependents left dangling on purpose by
+/// [`DependencyDespawnPolicy::Error`] are not reported.
+pub fn validate_attribute_graph(
+    attributes: Query<(Entity, &Attribute)>,
+    dynamic_modifiers: Query<(Entity, &DynamicModifier)>,
+    attribute_dependencies: Query<(Entity, &AttributeDependencies)>,
+    attribute_dependents: Query<(Entity, &AttributeDependents)>,
+    despawn_policies: Query<&DependencyDespawnPolicy>,
+    default_despawn_policy: Option<Res<DefaultDependencyDespawnPolicy>>,
+) -> Vec<AttributeGraphDiscrepancy> {
+    let default_despawn_policy = default_despawn_policy.map_or_else(Default::default, |p| p.0);
+    let mut discrepancies = Vec::new();
+    let mut expected = EntityHashMap::<EntityHashMap<usize>>::default();
+    let mut declare = |entity: Entity, dependency: Entity| {
+        let policy = despawn_policies
+            .get(entity)
+            .copied()
+            .unwrap_or(default_despawn_policy);
+        if attributes.contains(dependency) {
+            *expected
+                .entry(entity)
+                .or_default()
+                .entry(dependency)
+                .or_insert(0) += 1;
+        } else if policy != DependencyDespawnPolicy::Error {
+            discrepancies
+                .push(AttributeGraphDiscrepancy::DanglingDependency { entity, dependency });
+        }
+    };
+    for (entity, attribute) in &attributes {
+        match attribute {
+            Attribute::BasedOn(base_entity) => declare(entity, *base_entity),
+            Attribute::Merged(dependency_entities) => {
+                for dependency_entity in dependency_entities {
+                    declare(entity, *dependency_entity);
+                }
+            }
+            Attribute::Fixed => {}
+            Attribute::Plain(_) => {}
+        }
+    }
+    for (entity, dynamic_modifier) in &dynamic_modifiers {
+        declare(entity, dynamic_modifier.source);
+    }
+
+    for (entity, dependencies) in &attribute_dependencies {
+        if dependencies.is_empty() {
+            discrepancies.push(AttributeGraphDiscrepancy::EmptyDependencies(entity));
+        }
+        let expected_dependencies = expected.remove(&entity).unwrap_or_default();
+        for (dependency, actual) in dependencies.iter() {
+            let expected = expected_dependencies.get(dependency).copied().unwrap_or(0);
+            if expected != *actual {
+                discrepancies.push(AttributeGraphDiscrepancy::DependencyCountMismatch {
+                    entity,
+                    dependency: *dependency,
+                    expected,
+                    actual: *actual,
+                });
+            }
+            if *actual != 0
+                && !attribute_dependents
+                    .get(*dependency)
+                    .is_ok_and(|(_, dependents)| dependents.contains(&entity))
+            {
+                discrepancies.push(AttributeGraphDiscrepancy::MissingDependent {
+                    dependency: *dependency,
+                    dependent: entity,
+                });
+            }
+        }
+        for (dependency, expected) in expected_dependencies {
+            if !dependencies.contains_key(&dependency) {
+                discrepancies.push(AttributeGraphDiscrepancy::DependencyCountMismatch {
+                    entity,
+                    dependency,
+                    expected,
+                    actual: 0,
+                });
+            }
+        }
+    }
+    for (entity, expected_dependencies) in expected {
+        for (dependency, expected) in expected_dependencies {
+            discrepancies.push(AttributeGraphDiscrepancy::DependencyCountMismatch {
+                entity,
+                dependency,
+                expected,
+                actual: 0,
+            });
+        }
+    }
+
+    for (entity, dependents) in &attribute_dependents {
+        if dependents.is_empty() {
+            discrepancies.push(AttributeGraphDiscrepancy::EmptyDependents(entity));
+        }
+        for dependent in dependents.iter() {
+            if !attribute_dependencies
+                .get(*dependent)
+                .is_ok_and(|(_, dependencies)| {
+                    dependencies.get(&entity).is_some_and(|count| *count != 0)
+                })
+            {
+                discrepancies.push(AttributeGraphDiscrepancy::StaleDependent {
+                    dependency: entity,
+                    dependent: *dependent,
+                });
+            }
+        }
+    }
+
+    discrepancies.sort();
+    discrepancies
+}
+
+pub fn report_attribute_graph_discrepancies(In(discrepancies): In<Vec<AttributeGraphDiscrepancy>>) {
+    for discrepancy in discrepancies {
+        error!("attribute graph discrepancy: {:?}", discrepancy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{AttributePlugin, Modifier, Number, Scalar};
+    use bevy::ecs::entity::EntityHashSet;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_validate_attribute_graph() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let base = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world.spawn(Modifier::new(base, Scalar::ZERO, Scalar::from_f64(100.0)));
+        let delta = world.spawn(Attribute::BasedOn(base)).id();
+        let final_ = world
+            .spawn(Attribute::Merged(EntityHashSet::from_iter([base, delta])))
+            .id();
+        let other = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        let dynamic_modifier = world
+            .spawn(DynamicModifier::new_scale(
+                other,
+                final_,
+                Scalar::ZERO,
+                Scalar::ZERO,
+                Scalar::from_f64(0.1),
+            ))
+            .id();
+        world.flush();
+        assert_eq!(
+            world.run_system_once(validate_attribute_graph).unwrap(),
+            vec![]
+        );
+
+        let replacement = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        world
+            .entity_mut(delta)
+            .insert(Attribute::BasedOn(replacement));
+        world.despawn(dynamic_modifier);
+        world.flush();
+        assert_eq!(
+            world.run_system_once(validate_attribute_graph).unwrap(),
+            vec![]
+        );
+
+        world
+            .get_mut::<AttributeDependents>(base)
+            .unwrap()
+            .0
+            .remove(&final_);
+        assert_eq!(
+            world.run_system_once(validate_attribute_graph).unwrap(),
+            vec![
+                AttributeGraphDiscrepancy::MissingDependent {
+                    dependency: base,
+                    dependent: final_,
+                },
+                AttributeGraphDiscrepancy::EmptyDependents(base),
+            ]
+        );
+        world
+            .get_mut::<AttributeDependents>(base)
+            .unwrap()
+            .0
+            .insert(final_);
+
+        let doomed = world.spawn(Attribute::Plain(Scalar::ZERO)).id();
+        let dangling = world
+            .spawn((Attribute::BasedOn(doomed), DependencyDespawnPolicy::Error))
+            .id();
+        let zeroed = world.spawn(Attribute::BasedOn(doomed)).id();
+        world.flush();
+        world.despawn(doomed);
+        world.flush();
+        assert_eq!(
+            world.get::<Attribute>(dangling),
+            Some(&Attribute::BasedOn(doomed))
+        );
+        assert_eq!(
+            world.get::<Attribute>(zeroed),
+            Some(&Attribute::Plain(Scalar::ZERO))
+        );
+        assert_eq!(
+            world.run_system_once(validate_attribute_graph).unwrap(),
+            vec![]
+        );
+    }
+}
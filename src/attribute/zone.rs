@@ -1,4 +1,3 @@
-use crate::attribute::Attribute;
 use bevy::prelude::*;
 use std::fmt::Debug;
 use std::hash::Hash;
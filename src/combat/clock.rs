@@ -0,0 +1,141 @@
+use crate::attribute::{Number, Scalar};
+use bevy::prelude::*;
+
+/// Total action value elapsed in the battle and the cycle it falls into.
+/// Cycle 0 lasts `first_cycle_length` action value, every later cycle
+/// `cycle_length`; an action landing exactly on a boundary belongs to the
+/// earlier cycle.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct BattleClock {
+    pub elapsed: Scalar,
+    pub cycle: u32,
+    pub first_cycle_length: Scalar,
+    pub cycle_length: Scalar,
+    /// Whether the clock has advanced, i.e. cycle 0 has started.
+    pub started: bool,
+}
+
+impl Default for BattleClock {
+    fn default() -> Self {
+        Self {
+            elapsed: Scalar::ZERO,
+            cycle: 0,
+            first_cycle_length: Scalar::from_f64(150.0),
+            cycle_length: Scalar::from_f64(100.0),
+            started: false,
+        }
+    }
+}
+
+impl BattleClock {
+    pub fn cycle_end(&self, cycle: u32) -> Scalar {
+        self.first_cycle_length + self.cycle_length * Scalar::from_f64(cycle as f64)
+    }
+
+    pub fn remaining(&self) -> Scalar {
+        self.cycle_end(self.cycle) - self.elapsed
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleStartEvent {
+    pub cycle: u32,
+}
+
+/// Despawns the entity, typically a modifier, when the stored cycle starts.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExpiresAtCycle(pub u32);
+
+/// Adds `action_value` to the [`BattleClock`] and triggers a
+/// [`CycleStartEvent`] for cycle 0 on the first advance and for every cycle
+/// boundary crossed.
+pub fn advance_battle_clock(world: &mut World, action_value: Scalar) {
+    let Some(mut clock) = world.get_resource_mut::<BattleClock>() else {
+        return;
+    };
+    clock.elapsed += action_value;
+    let first_cycle = if clock.started {
+        clock.cycle + 1
+    } else {
+        clock.cycle
+    };
+    clock.started = true;
+    while clock.elapsed > clock.cycle_end(clock.cycle) {
+        clock.cycle += 1;
+    }
+    let last_cycle = clock.cycle;
+    for cycle in first_cycle..=last_cycle {
+        world.trigger(CycleStartEvent { cycle });
+    }
+}
+
+pub fn expire_at_cycle_start(
+    event: On<CycleStartEvent>,
+    expiring: Query<(Entity, &ExpiresAtCycle)>,
+    mut commands: Commands,
+) {
+    for (entity, expires_at) in &expiring {
+        if expires_at.0 <= event.cycle {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, AttributeValue, Modifier};
+    use crate::combat::{ActionGauge, CombatPlugin, next_actor};
+
+    #[derive(Resource, Default)]
+    struct Cycles(Vec<u32>);
+
+    #[test]
+    fn test_battle_clock_cycles() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Cycles>();
+        app.add_observer(|event: On<CycleStartEvent>, mut cycles: ResMut<Cycles>| {
+            cycles.0.push(event.cycle);
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let speed = world.spawn(Attribute::Plain(Scalar::from_f64(40.0))).id();
+        world.spawn(Modifier::new(speed, Scalar::ONE, Scalar::ZERO));
+        world.spawn(ActionGauge::new(speed));
+        let attack = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn((
+            Modifier::new(attack, Scalar::ZERO, Scalar::from_f64(100.0)),
+            ExpiresAtCycle(3),
+        ));
+        world.spawn((
+            Modifier::new(attack, Scalar::ONE, Scalar::ZERO),
+            ExpiresAtCycle(5),
+        ));
+        world.spawn((
+            Modifier::new(attack, Scalar::ZERO, Scalar::from_f64(50.0)),
+            ExpiresAtCycle(0),
+        ));
+        app.update();
+        let attack_value = |app: &App| **app.world().get::<AttributeValue>(attack).unwrap();
+        assert_eq!(attack_value(&app), Some(Scalar::from_f64(250.0)));
+
+        next_actor(app.world_mut());
+        app.update();
+        let clock = *app.world().resource::<BattleClock>();
+        assert_eq!((clock.elapsed, clock.cycle), (Scalar::from_f64(250.0), 1));
+        assert_eq!(clock.remaining(), Scalar::ZERO);
+        assert_eq!(app.world().resource::<Cycles>().0, vec![0, 1]);
+        assert_eq!(attack_value(&app), Some(Scalar::from_f64(200.0)));
+
+        next_actor(app.world_mut());
+        app.update();
+        let clock = *app.world().resource::<BattleClock>();
+        assert_eq!((clock.elapsed, clock.cycle), (Scalar::from_f64(500.0), 4));
+        assert_eq!(clock.remaining(), Scalar::from_f64(50.0));
+        assert_eq!(app.world().resource::<Cycles>().0, vec![0, 1, 2, 3, 4]);
+        assert_eq!(attack_value(&app), Some(Scalar::from_f64(100.0)));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::attribute::{Number, Scalar};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rand::prelude::{GlobalRng, WyRand};
+use rand_core::RngCore;
+
+/// How a battle resolves critical hits.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CritMode {
+    /// Roll against Crit Rate with the attacker's own RNG stream, seeded
+    /// through `RngSeed<WyRand>`, or the global one when it has none.
+    #[default]
+    Roll,
+    /// Multiply every hit by `1 + CR × CD`.
+    ExpectedValue,
+}
+
+#[derive(SystemParam)]
+pub struct CriticalHits<'w, 's> {
+    pub crit_mode: Option<Res<'w, CritMode>>,
+    pub rngs: Query<'w, 's, &'static mut WyRand>,
+    pub global: Query<'w, 's, Entity, (With<WyRand>, With<GlobalRng>)>,
+}
+
+impl CriticalHits<'_, '_> {
+    /// Uniform value in `[0, 1)` from the attacker's stream, or `None` when
+    /// neither it nor a global RNG exists.
+    pub fn sample(&mut self, attacker: Entity) -> Option<f64> {
+        let entity = if self.rngs.contains(attacker) {
+            attacker
+        } else {
+            self.global.single().ok()?
+        };
+        let mut rng = self.rngs.get_mut(entity).ok()?;
+        Some(rng.next_u32() as f64 / (u32::MAX as f64 + 1.0))
+    }
+
+    /// Returns the critical multiplier and whether the hit was critical.
+    /// Crit Rate is clamped to `[0, 1]`.
+    pub fn factor(
+        &mut self,
+        attacker: Entity,
+        crit_rate: Scalar,
+        crit_damage: Scalar,
+    ) -> (Scalar, bool) {
+        let crit_rate = crit_rate.clamp(Scalar::ZERO, Scalar::ONE);
+        match self.crit_mode.as_deref().copied().unwrap_or_default() {
+            CritMode::ExpectedValue => (Scalar::ONE + crit_rate * crit_damage, false),
+            CritMode::Roll => {
+                let Some(sample) = self.sample(attacker) else {
+                    warn!("no WyRand for {attacker} or a GlobalRng to roll critical hits with");
+                    return (Scalar::ONE, false);
+                };
+                let is_critical = sample < crit_rate.to_f64();
+                if is_critical {
+                    (Scalar::ONE + crit_damage, true)
+                } else {
+                    (Scalar::ONE, false)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributeOf, AttributePlugin, AttributeType, Modifier};
+    use crate::combat::{CombatPlugin, DamageEvent, DamageRequest, Element, deal_damage};
+    use bevy_rand::prelude::{EntropyPlugin, RngSeed, SeedSource};
+
+    #[derive(Resource, Default)]
+    struct Criticals(Vec<(bool, Scalar)>);
+
+    fn run_battle(
+        crit_mode: CritMode,
+        seed: Option<u64>,
+        crit_rate: Scalar,
+    ) -> Vec<(bool, Scalar)> {
+        let mut app = App::new();
+        if seed.is_some() {
+            app.add_plugins(EntropyPlugin::<WyRand>::with_seed(7u64.to_le_bytes()));
+        }
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.insert_resource(crit_mode);
+        app.init_resource::<Criticals>();
+        app.add_observer(|event: On<DamageEvent>, mut criticals: ResMut<Criticals>| {
+            criticals
+                .0
+                .push((event.breakdown.is_critical, event.breakdown.critical));
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let attacker = world.spawn_empty().id();
+        if let Some(seed) = seed {
+            world
+                .entity_mut(attacker)
+                .insert(RngSeed::<WyRand>::from_seed(seed.to_le_bytes()));
+        }
+        for (attribute_type, value) in [
+            (AttributeType::Attack, Scalar::from_f64(1000.0)),
+            (AttributeType::CriticalChance, crit_rate),
+            (AttributeType::CriticalDamage, Scalar::ONE),
+        ] {
+            let attribute = world
+                .spawn((
+                    Attribute::Plain(value),
+                    attribute_type,
+                    AttributeOf(attacker),
+                ))
+                .id();
+            world.spawn(Modifier::new(attribute, Scalar::ONE, Scalar::ZERO));
+        }
+        let target = world.spawn_empty().id();
+        app.update();
+
+        for _ in 0..200 {
+            app.world_mut()
+                .commands()
+                .queue(deal_damage(DamageRequest::new(
+                    attacker,
+                    target,
+                    Element::Physical,
+                    Scalar::ONE,
+                )));
+        }
+        app.world_mut().flush();
+        app.world_mut().remove_resource::<Criticals>().unwrap().0
+    }
+
+    #[test]
+    fn test_critical_hits() {
+        let rolls = run_battle(CritMode::Roll, Some(42), Scalar::from_f64(0.5));
+        assert_eq!(
+            rolls,
+            run_battle(CritMode::Roll, Some(42), Scalar::from_f64(0.5))
+        );
+        assert_ne!(
+            rolls,
+            run_battle(CritMode::Roll, Some(43), Scalar::from_f64(0.5))
+        );
+        let criticals = rolls.iter().filter(|(is_critical, _)| *is_critical).count();
+        assert!((60..=140).contains(&criticals));
+        assert!(rolls.iter().all(|&(is_critical, critical)| {
+            critical
+                == if is_critical {
+                    Scalar::from_f64(2.0)
+                } else {
+                    Scalar::ONE
+                }
+        }));
+
+        let expected = run_battle(CritMode::ExpectedValue, Some(42), Scalar::from_f64(0.5));
+        assert!(
+            expected
+                .iter()
+                .all(|&critical| critical == (false, Scalar::from_f64(1.5)))
+        );
+
+        // `CombatPlugin` alone provides an RNG to roll with.
+        let unseeded = run_battle(CritMode::Roll, None, Scalar::ONE);
+        assert!(
+            unseeded
+                .iter()
+                .all(|&critical| critical == (true, Scalar::from_f64(2.0)))
+        );
+    }
+}
@@ -0,0 +1,456 @@
+use crate::attribute::{
+    AttributeEvaluator, AttributeQueries, AttributeType, EvaluationContext, FinalZoneAttribute,
+    ModifierConditionQueries, Number, OwnedAttributes, Scalar, Tags,
+};
+use crate::combat::CriticalHits;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Element {
+    Physical,
+    Fire,
+    Ice,
+    Lightning,
+    Wind,
+    Quantum,
+    Imaginary,
+}
+
+impl Element {
+    pub fn damage_type(self) -> AttributeType {
+        match self {
+            Element::Physical => AttributeType::PhysicalDamage,
+            Element::Fire => AttributeType::FireDamage,
+            Element::Ice => AttributeType::IceDamage,
+            Element::Lightning => AttributeType::TunderDamage,
+            Element::Wind => AttributeType::WindDamage,
+            Element::Quantum => AttributeType::QuantumDamage,
+            Element::Imaginary => AttributeType::ImaginaryDamage,
+        }
+    }
+
+    pub fn resistance_type(self) -> AttributeType {
+        match self {
+            Element::Physical => AttributeType::PhysicalResistance,
+            Element::Fire => AttributeType::FireResistance,
+            Element::Ice => AttributeType::IceResistance,
+            Element::Lightning => AttributeType::ThunderResistance,
+            Element::Wind => AttributeType::WindResistance,
+            Element::Quantum => AttributeType::QuantumResistance,
+            Element::Imaginary => AttributeType::ImaginaryResistance,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Level(pub u32);
+
+/// Present while the enemy's toughness is broken.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WeaknessBroken;
+
+/// Looks up combat stats among the attributes a combatant owns through
+/// [`AttributeOf`](crate::attribute::AttributeOf), preferring the
+/// [`FinalZoneAttribute`] of a type.
+#[derive(SystemParam)]
+pub struct CombatantAttributes<'w, 's> {
+    pub owned_attributes: Query<'w, 's, &'static OwnedAttributes>,
+    pub attribute_types: Query<'w, 's, (&'static AttributeType, Has<FinalZoneAttribute>)>,
+    pub condition_queries: ModifierConditionQueries<'w, 's>,
+}
+
+impl CombatantAttributes<'_, '_> {
+    pub fn find(&self, owner: Entity, attribute_type: AttributeType) -> Option<Entity> {
+        self.owned_attributes
+            .get(owner)
+            .ok()?
+            .iter()
+            .filter_map(|attribute| match self.attribute_types.get(attribute) {
+                Ok((t, is_final)) if *t == attribute_type => Some((attribute, is_final)),
+                _ => None,
+            })
+            .min_by_key(|(attribute, is_final)| (!is_final, attribute.index()))
+            .map(|(attribute, _)| attribute)
+    }
+
+    /// Value of the stat evaluated in `context`, zero when the combatant
+    /// doesn't have it.
+    pub fn value(
+        &self,
+        attribute_queries: &mut AttributeQueries,
+        attribute_evaluator: &mut AttributeEvaluator,
+        owner: Entity,
+        attribute_type: AttributeType,
+        context: &EvaluationContext,
+    ) -> Scalar {
+        self.find(owner, attribute_type)
+            .and_then(|attribute| {
+                attribute_evaluator.fetch_value_in_context(
+                    attribute_queries,
+                    &self.condition_queries,
+                    attribute,
+                    context,
+                )
+            })
+            .unwrap_or(Scalar::ZERO)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DamageKind {
+    #[default]
+    Direct,
+    /// Weakness break damage: boosted by Break Effect instead of DMG%, never
+    /// critical and always dealt as against an unbroken target.
+    Break,
+    /// Damage of a break effect such as Burn, boosted and never critical like
+    /// [`DamageKind::Break`] but taking the target's broken state into account.
+    BreakEffect,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DamageRequest {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub element: Element,
+    /// Stat of the attacker the ability scales with, usually Attack.
+    pub scaling: AttributeType,
+    pub multiplier: Scalar,
+    pub flat: Scalar,
+    pub action_tags: Tags,
+    pub kind: DamageKind,
+}
+
+impl DamageRequest {
+    pub fn new(attacker: Entity, target: Entity, element: Element, multiplier: Scalar) -> Self {
+        Self {
+            attacker,
+            target,
+            element,
+            scaling: AttributeType::Attack,
+            multiplier,
+            flat: Scalar::ZERO,
+            action_tags: Tags::default(),
+            kind: DamageKind::Direct,
+        }
+    }
+
+    /// Break damage worth `base` before the attacker's and target's factors.
+    pub fn break_damage(attacker: Entity, target: Entity, element: Element, base: Scalar) -> Self {
+        Self {
+            multiplier: Scalar::ZERO,
+            flat: base,
+            kind: DamageKind::Break,
+            ..Self::new(attacker, target, element, Scalar::ZERO)
+        }
+    }
+
+    pub fn with_kind(mut self, kind: DamageKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_action_tags(mut self, action_tags: Tags) -> Self {
+        self.action_tags = action_tags;
+        self
+    }
+}
+
+/// Every factor of a damage instance; `total` is their product.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DamageBreakdown {
+    pub base: Scalar,
+    pub damage_boost: Scalar,
+    pub weaken: Scalar,
+    pub defense: Scalar,
+    pub resistance: Scalar,
+    pub vulnerability: Scalar,
+    pub mitigation: Scalar,
+    pub broken: Scalar,
+    pub critical: Scalar,
+    /// Whether the hit was rolled as critical; always `false` in
+    /// [`CritMode::ExpectedValue`](crate::combat::CritMode::ExpectedValue).
+    pub is_critical: bool,
+    pub total: Scalar,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct DamageEvent {
+    /// The target.
+    pub entity: Entity,
+    pub attacker: Entity,
+    pub element: Element,
+    pub kind: DamageKind,
+    pub breakdown: DamageBreakdown,
+}
+
+pub fn calculate_damage(
+    In(request): In<DamageRequest>,
+    combatant_attributes: CombatantAttributes,
+    combatants: Query<(Option<&Level>, Has<WeaknessBroken>, Option<&Tags>)>,
+    mut critical_hits: CriticalHits,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) -> DamageBreakdown {
+    let context = EvaluationContext::new(request.attacker, request.target)
+        .with_target_tags(
+            combatants
+                .get(request.target)
+                .ok()
+                .and_then(|(_, _, tags)| tags.cloned())
+                .unwrap_or_default(),
+        )
+        .with_action_tags(request.action_tags.clone());
+    let mut stat = |owner, attribute_type| {
+        combatant_attributes.value(
+            &mut attribute_queries,
+            &mut attribute_evaluator,
+            owner,
+            attribute_type,
+            &context,
+        )
+    };
+    let attacker = request.attacker;
+    let target = request.target;
+
+    let is_break = matches!(request.kind, DamageKind::Break | DamageKind::BreakEffect);
+
+    let base = stat(attacker, request.scaling) * request.multiplier + request.flat;
+    let damage_boost = if is_break {
+        Scalar::ONE + stat(attacker, AttributeType::BreakDamage)
+    } else {
+        Scalar::ONE
+            + stat(attacker, request.element.damage_type())
+            + stat(attacker, AttributeType::DamageBoost)
+    };
+    let weaken = Scalar::ONE - stat(attacker, AttributeType::Weaken);
+    let level = Scalar::from_f64(
+        combatants
+            .get(attacker)
+            .ok()
+            .and_then(|(level, _, _)| level)
+            .map_or(80, |level| level.0) as f64,
+    );
+    let target_defense = stat(target, AttributeType::Defense)
+        * (Scalar::ONE - stat(attacker, AttributeType::DefenseIgnore));
+    let target_defense = if target_defense < Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        target_defense
+    };
+    let defense = Scalar::ONE
+        - target_defense
+            / (target_defense + Scalar::from_f64(200.0) + Scalar::from_f64(10.0) * level);
+    let resistance = (Scalar::ONE - stat(target, request.element.resistance_type())
+        + stat(attacker, AttributeType::ResistancePenetration))
+    .clamp(Scalar::from_f64(0.1), Scalar::from_f64(2.0));
+    let vulnerability = Scalar::ONE + stat(target, AttributeType::Vulnerability);
+    let mitigation = Scalar::ONE - stat(target, AttributeType::DamageMitigation);
+    let broken = if request.kind != DamageKind::Break
+        && combatants.get(target).is_ok_and(|(_, broken, _)| broken)
+    {
+        Scalar::ONE
+    } else {
+        Scalar::from_f64(0.9)
+    };
+    let (critical, is_critical) = if is_break {
+        (Scalar::ONE, false)
+    } else {
+        critical_hits.factor(
+            attacker,
+            stat(attacker, AttributeType::CriticalChance),
+            stat(attacker, AttributeType::CriticalDamage),
+        )
+    };
+    DamageBreakdown {
+        base,
+        damage_boost,
+        weaken,
+        defense,
+        resistance,
+        vulnerability,
+        mitigation,
+        broken,
+        critical,
+        is_critical,
+        total: base
+            * critical
+            * damage_boost
+            * weaken
+            * defense
+            * resistance
+            * vulnerability
+            * mitigation
+            * broken,
+    }
+}
+
+/// Command calculating the damage of `request` and triggering
+/// [`DamageEvent`] on the target.
+pub fn deal_damage(request: DamageRequest) -> impl Command {
+    move |world: &mut World| {
+        let attacker = request.attacker;
+        let target = request.target;
+        let element = request.element;
+        let kind = request.kind;
+        if let Ok(breakdown) = world.run_system_cached_with(calculate_damage, request) {
+            world.trigger(DamageEvent {
+                entity: target,
+                attacker,
+                element,
+                kind,
+                breakdown,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{
+        Attribute, AttributeOf, AttributePlugin, BaseZoneAttribute, Modifier, ModifierContext, Tag,
+    };
+    use crate::combat::CombatPlugin;
+
+    #[derive(Resource, Default)]
+    struct Damage(Vec<DamageEvent>);
+
+    fn spawn_stat(
+        world: &mut World,
+        owner: Entity,
+        attribute_type: AttributeType,
+        value: Scalar,
+    ) -> Entity {
+        let attribute = world
+            .spawn((
+                Attribute::Plain(value),
+                attribute_type,
+                FinalZoneAttribute,
+                AttributeOf(owner),
+            ))
+            .id();
+        world.spawn(Modifier::new(attribute, Scalar::ONE, Scalar::ZERO));
+        attribute
+    }
+
+    #[test]
+    fn test_damage_pipeline() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Damage>();
+        app.add_observer(|event: On<DamageEvent>, mut damage: ResMut<Damage>| {
+            damage.0.push(*event);
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let attacker = world.spawn(Level(80)).id();
+        world.spawn((
+            Attribute::Plain(Scalar::ONE),
+            AttributeType::Attack,
+            BaseZoneAttribute,
+            AttributeOf(attacker),
+        ));
+        spawn_stat(
+            world,
+            attacker,
+            AttributeType::Attack,
+            Scalar::from_f64(1000.0),
+        );
+        spawn_stat(
+            world,
+            attacker,
+            AttributeType::FireDamage,
+            Scalar::from_f64(0.5),
+        );
+        let damage_boost = spawn_stat(
+            world,
+            attacker,
+            AttributeType::DamageBoost,
+            Scalar::from_f64(0.25),
+        );
+        world.spawn((
+            Modifier::new(damage_boost, Scalar::ZERO, Scalar::from_f64(0.25)),
+            ModifierContext::default().with_action_tags(Tags::new([Tag("ultimate")])),
+        ));
+        spawn_stat(
+            world,
+            attacker,
+            AttributeType::DefenseIgnore,
+            Scalar::from_f64(0.2),
+        );
+        spawn_stat(
+            world,
+            attacker,
+            AttributeType::ResistancePenetration,
+            Scalar::from_f64(0.1),
+        );
+        let target = world.spawn_empty().id();
+        spawn_stat(
+            world,
+            target,
+            AttributeType::Defense,
+            Scalar::from_f64(1000.0),
+        );
+        spawn_stat(
+            world,
+            target,
+            AttributeType::FireResistance,
+            Scalar::from_f64(0.2),
+        );
+        spawn_stat(
+            world,
+            target,
+            AttributeType::Vulnerability,
+            Scalar::from_f64(0.25),
+        );
+        spawn_stat(
+            world,
+            target,
+            AttributeType::DamageMitigation,
+            Scalar::from_f64(0.2),
+        );
+        app.update();
+
+        app.world_mut()
+            .commands()
+            .queue(deal_damage(DamageRequest::new(
+                attacker,
+                target,
+                Element::Fire,
+                Scalar::from_f64(2.0),
+            )));
+        app.world_mut().flush();
+        app.world_mut().entity_mut(target).insert(WeaknessBroken);
+        app.world_mut().commands().queue(deal_damage(
+            DamageRequest::new(attacker, target, Element::Fire, Scalar::from_f64(2.0))
+                .with_action_tags(Tags::new([Tag("ultimate")])),
+        ));
+        app.world_mut().flush();
+
+        let damage = &app.world().resource::<Damage>().0;
+        assert_eq!(damage.len(), 2);
+        let breakdown = damage[0].breakdown;
+        assert_eq!(damage[0].entity, target);
+        assert_eq!(damage[0].attacker, attacker);
+        assert_eq!(breakdown.base, Scalar::from_f64(2000.0));
+        assert_eq!(breakdown.damage_boost, Scalar::from_f64(1.75));
+        assert_eq!(breakdown.weaken, Scalar::ONE);
+        assert!((breakdown.defense.to_f64() - 1000.0 / 1800.0).abs() < 1e-6);
+        assert!((breakdown.resistance.to_f64() - 0.9).abs() < 1e-6);
+        assert_eq!(breakdown.vulnerability, Scalar::from_f64(1.25));
+        assert!((breakdown.mitigation.to_f64() - 0.8).abs() < 1e-6);
+        assert!((breakdown.broken.to_f64() - 0.9).abs() < 1e-6);
+        let expected = 2000.0 * 1.75 * (1000.0 / 1800.0) * 0.9 * 1.25 * 0.8 * 0.9;
+        assert!((breakdown.total.to_f64() - expected).abs() < 1e-2);
+
+        let breakdown = damage[1].breakdown;
+        assert_eq!(breakdown.damage_boost, Scalar::from_f64(2.0));
+        assert_eq!(breakdown.broken, Scalar::ONE);
+        let expected = 2000.0 * 2.0 * (1000.0 / 1800.0) * 0.9 * 1.25 * 0.8;
+        assert!((breakdown.total.to_f64() - expected).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::attribute::{AttributeEvaluator, AttributeQueries, Number, Scalar};
+use bevy::prelude::*;
+
+/// Energy of a character. `max` points at its [`AttributeType::MaxSP`]
+/// attribute and `regeneration` at its [`AttributeType::SPRegen`] one, whose
+/// value is the bonus on top of the base Energy Regeneration Rate of 100%.
+///
+/// [`AttributeType::MaxSP`]: crate::attribute::AttributeType::MaxSP
+/// [`AttributeType::SPRegen`]: crate::attribute::AttributeType::SPRegen
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Energy {
+    pub current: Scalar,
+    pub max: Entity,
+    pub regeneration: Option<Entity>,
+}
+
+impl Energy {
+    pub fn new(max: Entity, regeneration: Option<Entity>) -> Self {
+        Self {
+            current: Scalar::ZERO,
+            max,
+            regeneration,
+        }
+    }
+}
+
+/// Present while the character has full energy.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UltimateReady;
+
+/// Where energy comes from. Everything except `Fixed` is scaled by the Energy
+/// Regeneration Rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EnergySource {
+    Action,
+    HitTaken,
+    Kill,
+    Fixed,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct EnergyGainedEvent {
+    pub entity: Entity,
+    pub source: EnergySource,
+    /// Energy actually gained after scaling and capping.
+    pub amount: Scalar,
+    pub current: Scalar,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UltimateReadyEvent {
+    pub entity: Entity,
+}
+
+fn update_ultimate_ready(
+    entity: Entity,
+    current: Scalar,
+    max: Scalar,
+    ready: bool,
+    commands: &mut Commands,
+) {
+    let full = current >= max && max > Scalar::ZERO;
+    if full && !ready {
+        commands.entity(entity).insert(UltimateReady);
+        commands.trigger(UltimateReadyEvent { entity });
+    } else if !full && ready {
+        commands.entity(entity).remove::<UltimateReady>();
+    }
+}
+
+fn apply_energy_gain(
+    In((entity, amount, source)): In<(Entity, Scalar, EnergySource)>,
+    mut energies: Query<(&mut Energy, Has<UltimateReady>)>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+    mut commands: Commands,
+) {
+    let Ok((mut energy, ready)) = energies.get_mut(entity) else {
+        return;
+    };
+    let mut fetch = |attribute| {
+        attribute_evaluator
+            .fetch_value(&mut attribute_queries, attribute)
+            .unwrap_or(Scalar::ZERO)
+    };
+    let rate = match (source, energy.regeneration) {
+        (EnergySource::Fixed, _) | (_, None) => Scalar::ONE,
+        (_, Some(regeneration)) => Scalar::ONE + fetch(regeneration),
+    };
+    let max = fetch(energy.max);
+    let previous = energy.current;
+    energy.current += amount * rate;
+    if energy.current > max {
+        energy.current = max;
+    }
+    update_ultimate_ready(entity, energy.current, max, ready, &mut commands);
+    commands.trigger(EnergyGainedEvent {
+        entity,
+        source,
+        amount: energy.current - previous,
+        current: energy.current,
+    });
+}
+
+/// Entity command adding `amount` energy from `source` to the character.
+pub fn gain_energy(amount: Scalar, source: EnergySource) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let id = entity.id();
+        entity.world_scope(|world| {
+            let _ = world.run_system_cached_with(apply_energy_gain, (id, amount, source));
+        });
+    }
+}
+
+/// Entity command emptying the character's energy, as casting an Ultimate
+/// does.
+pub fn reset_energy(mut entity: EntityWorldMut) {
+    if let Some(mut energy) = entity.get_mut::<Energy>() {
+        energy.current = Scalar::ZERO;
+        entity.remove::<UltimateReady>();
+    }
+}
+
+/// Caps energy and refreshes [`UltimateReady`] after max energy changes.
+/// [`Energy`] is only marked changed when it actually gets capped.
+pub fn sync_ultimate_ready(
+    mut energies: Query<(Entity, &mut Energy, Has<UltimateReady>)>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+    mut commands: Commands,
+) {
+    for (entity, mut energy, ready) in &mut energies {
+        let max = attribute_evaluator
+            .fetch_value(&mut attribute_queries, energy.max)
+            .unwrap_or(Scalar::ZERO);
+        if energy.current > max {
+            energy.current = max;
+        }
+        update_ultimate_ready(entity, energy.current, max, ready, &mut commands);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, AttributeType, Modifier};
+    use crate::combat::CombatPlugin;
+
+    #[derive(Resource, Default)]
+    struct Log {
+        gained: Vec<Scalar>,
+        ready: usize,
+        energy_changed: bool,
+    }
+
+    #[test]
+    fn test_energy_regeneration_and_ultimate_ready() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Log>();
+        app.add_observer(|event: On<EnergyGainedEvent>, mut log: ResMut<Log>| {
+            log.gained.push(event.amount);
+        });
+        app.add_observer(|_: On<UltimateReadyEvent>, mut log: ResMut<Log>| log.ready += 1);
+        app.add_systems(
+            Last,
+            |energies: Query<Ref<Energy>>, mut log: ResMut<Log>| {
+                log.energy_changed = energies.iter().any(|energy| energy.is_changed());
+            },
+        );
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let max = world
+            .spawn((
+                Attribute::Plain(Scalar::from_f64(120.0)),
+                AttributeType::MaxSP,
+            ))
+            .id();
+        world.spawn(Modifier::new(max, Scalar::ONE, Scalar::ZERO));
+        let regeneration = world
+            .spawn((Attribute::Plain(Scalar::ZERO), AttributeType::SPRegen))
+            .id();
+        world.spawn(Modifier::new(
+            regeneration,
+            Scalar::ZERO,
+            Scalar::from_f64(0.25),
+        ));
+        let character = world.spawn(Energy::new(max, Some(regeneration))).id();
+        app.update();
+
+        let gain = |app: &mut App, amount: Scalar, source: EnergySource| {
+            app.world_mut()
+                .commands()
+                .entity(character)
+                .queue(gain_energy(amount, source));
+            app.world_mut().flush();
+            app.world().get::<Energy>(character).unwrap().current
+        };
+        assert_eq!(
+            gain(&mut app, Scalar::from_f64(30.0), EnergySource::Action),
+            Scalar::from_f64(37.5)
+        );
+        assert_eq!(
+            gain(&mut app, Scalar::from_f64(60.0), EnergySource::HitTaken),
+            Scalar::from_f64(112.5)
+        );
+        assert_eq!(
+            gain(&mut app, Scalar::from_f64(5.0), EnergySource::Fixed),
+            Scalar::from_f64(117.5)
+        );
+        assert!(!app.world().entity(character).contains::<UltimateReady>());
+        assert_eq!(
+            gain(&mut app, Scalar::from_f64(10.0), EnergySource::Kill),
+            Scalar::from_f64(120.0)
+        );
+        assert!(app.world().entity(character).contains::<UltimateReady>());
+
+        app.world_mut()
+            .commands()
+            .entity(character)
+            .queue(reset_energy);
+        app.world_mut().flush();
+        assert!(!app.world().entity(character).contains::<UltimateReady>());
+        assert_eq!(
+            gain(&mut app, Scalar::from_f64(100.0), EnergySource::Fixed),
+            Scalar::from_f64(100.0)
+        );
+
+        app.world_mut()
+            .spawn(Modifier::new(max, Scalar::ZERO, Scalar::from_f64(-40.0)));
+        app.update();
+        assert_eq!(
+            app.world().get::<Energy>(character).unwrap().current,
+            Scalar::from_f64(80.0)
+        );
+        assert!(app.world().entity(character).contains::<UltimateReady>());
+        assert!(app.world().resource::<Log>().energy_changed);
+
+        // Syncing without anything to cap leaves `Energy` unchanged.
+        app.update();
+        assert!(!app.world().resource::<Log>().energy_changed);
+
+        let log = app.world().resource::<Log>();
+        assert_eq!(
+            log.gained,
+            vec![
+                Scalar::from_f64(37.5),
+                Scalar::from_f64(75.0),
+                Scalar::from_f64(5.0),
+                Scalar::from_f64(2.5),
+                Scalar::from_f64(100.0)
+            ]
+        );
+        assert_eq!(log.ready, 2);
+    }
+}
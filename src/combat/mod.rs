@@ -0,0 +1,21 @@
+mod clock;
+mod crit;
+mod damage;
+mod energy;
+mod plugin;
+mod queue;
+mod skill_point;
+mod timeline;
+mod toughness;
+mod turn;
+
+pub use clock::*;
+pub use crit::*;
+pub use damage::*;
+pub use energy::*;
+pub use plugin::*;
+pub use queue::*;
+pub use skill_point::*;
+pub use timeline::*;
+pub use toughness::*;
+pub use turn::*;
@@ -0,0 +1,32 @@
+use crate::attribute::AttributeSystems;
+use crate::combat::{
+    ActionQueue, BattleClock, BreakLevelMultipliers, CritMode, TurnState, clamp_skill_points,
+    expire_at_cycle_start, resolve_break_at_turn_start, stack_entanglement_on_hit,
+    sync_ultimate_ready,
+};
+use bevy::prelude::*;
+use bevy_rand::prelude::{EntropyPlugin, WyRand};
+
+/// Adds an unseeded `EntropyPlugin<WyRand>` for critical rolls unless one was
+/// added before it.
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EntropyPlugin<WyRand>>() {
+            app.add_plugins(EntropyPlugin::<WyRand>::default());
+        }
+        app.add_observer(expire_at_cycle_start)
+            .add_observer(resolve_break_at_turn_start)
+            .add_observer(stack_entanglement_on_hit)
+            .init_resource::<BattleClock>()
+            .init_resource::<TurnState>()
+            .init_resource::<ActionQueue>()
+            .init_resource::<CritMode>()
+            .init_resource::<BreakLevelMultipliers>()
+            .add_systems(
+                PostUpdate,
+                (clamp_skill_points, sync_ultimate_ready).after(AttributeSystems::Evaluate),
+            );
+    }
+}
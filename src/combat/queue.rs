@@ -0,0 +1,170 @@
+use crate::combat::{ActionKind, Energy, UltimateReady, reset_energy, use_action};
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InsertedAction {
+    pub actor: Entity,
+    pub kind: ActionKind,
+}
+
+impl InsertedAction {
+    /// Lower resolves first: Ultimates, then counters, then follow-ups.
+    pub fn priority(&self) -> u8 {
+        match self.kind {
+            ActionKind::Ultimate => 0,
+            ActionKind::Counter => 1,
+            ActionKind::FollowUp => 2,
+            ActionKind::Basic | ActionKind::Skill => 3,
+        }
+    }
+}
+
+/// Actions inserted between timeline turns. Entries resolve by priority and
+/// then in insertion order.
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct ActionQueue {
+    entries: Vec<(u64, InsertedAction)>,
+    sequence: u64,
+}
+
+impl ActionQueue {
+    pub fn push(&mut self, action: InsertedAction) {
+        self.entries.push((self.sequence, action));
+        self.sequence += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<InsertedAction> {
+        let index = self
+            .entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (sequence, action))| (action.priority(), *sequence))?
+            .0;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Entity command inserting an action of the combatant into the
+/// [`ActionQueue`].
+pub fn insert_action(kind: ActionKind) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let actor = entity.id();
+        entity.world_scope(|world| {
+            if let Some(mut action_queue) = world.get_resource_mut::<ActionQueue>() {
+                action_queue.push(InsertedAction { actor, kind });
+            }
+        });
+    }
+}
+
+/// Command resolving queued actions, including those queued while resolving,
+/// until the queue is empty. An Ultimate of a character with [`Energy`] is
+/// skipped unless it is [`UltimateReady`], and empties the energy otherwise.
+pub fn resolve_action_queue(world: &mut World) {
+    while let Some(action) = world
+        .get_resource_mut::<ActionQueue>()
+        .and_then(|mut action_queue| action_queue.pop())
+    {
+        let Ok(actor) = world.get_entity(action.actor) else {
+            continue;
+        };
+        if action.kind == ActionKind::Ultimate && actor.contains::<Energy>() {
+            if !actor.contains::<UltimateReady>() {
+                continue;
+            }
+            reset_energy(world.entity_mut(action.actor));
+        }
+        use_action(action.kind).apply(world.entity_mut(action.actor));
+        world.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, Modifier, Number, Scalar};
+    use crate::combat::{
+        ActionGauge, ActionUsedEvent, CombatPlugin, TurnStartEvent, start_next_turn,
+    };
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(Entity, Option<ActionKind>)>);
+
+    #[test]
+    fn test_action_queue_resolves_before_next_turn() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Log>();
+        app.add_observer(|event: On<ActionUsedEvent>, mut log: ResMut<Log>| {
+            log.0.push((event.entity, Some(event.kind)));
+        });
+        app.add_observer(|event: On<TurnStartEvent>, mut log: ResMut<Log>| {
+            log.0.push((event.entity, None));
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let mut spawn_combatant = |speed: Scalar| {
+            let speed_attribute = world.spawn(Attribute::Plain(speed)).id();
+            world.spawn(Modifier::new(speed_attribute, Scalar::ONE, Scalar::ZERO));
+            world.spawn(ActionGauge::new(speed_attribute)).id()
+        };
+        let a = spawn_combatant(Scalar::from_f64(100.0));
+        let b = spawn_combatant(Scalar::from_f64(90.0));
+        let c = spawn_combatant(Scalar::from_f64(80.0));
+        let max_energy = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn(Modifier::new(max_energy, Scalar::ONE, Scalar::ZERO));
+        let unready = world.spawn(Energy::new(max_energy, None)).id();
+        // `c` follows up whenever it counters.
+        world
+            .entity_mut(c)
+            .observe(|event: On<ActionUsedEvent>, mut commands: Commands| {
+                if event.kind == ActionKind::Counter {
+                    commands
+                        .entity(event.entity)
+                        .queue(insert_action(ActionKind::FollowUp));
+                }
+            });
+        app.update();
+
+        start_next_turn(app.world_mut());
+        for (actor, kind) in [
+            (a, ActionKind::FollowUp),
+            (b, ActionKind::Ultimate),
+            (unready, ActionKind::Ultimate),
+            (c, ActionKind::Counter),
+            (a, ActionKind::Ultimate),
+        ] {
+            app.world_mut()
+                .commands()
+                .entity(actor)
+                .queue(insert_action(kind));
+        }
+        app.world_mut().flush();
+        assert_eq!(app.world().resource::<ActionQueue>().len(), 5);
+        start_next_turn(app.world_mut());
+
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec![
+                (a, None),
+                (b, Some(ActionKind::Ultimate)),
+                (a, Some(ActionKind::Ultimate)),
+                (c, Some(ActionKind::Counter)),
+                (a, Some(ActionKind::FollowUp)),
+                (c, Some(ActionKind::FollowUp)),
+                (b, None),
+            ]
+        );
+        assert!(app.world().resource::<ActionQueue>().is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::attribute::{AttributeEvaluator, AttributeQueries, Number, TeamMember};
+use crate::combat::ActionKind;
+use bevy::prelude::*;
+
+/// Team-wide Skill Point pool. The cap is read from the `cap` attribute so
+/// modifiers can raise it.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SkillPoints {
+    pub current: u32,
+    pub cap: Entity,
+}
+
+impl SkillPoints {
+    pub fn new(cap: Entity) -> Self {
+        Self { current: 3, cap }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkillPointError {
+    MissingPool,
+    /// The cached pool system could not run.
+    SystemFailed,
+    Insufficient {
+        required: u32,
+        available: u32,
+    },
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SkillPointsGeneratedEvent {
+    pub entity: Entity,
+    /// Points actually gained after applying the cap.
+    pub amount: u32,
+    pub current: u32,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SkillPointsConsumedEvent {
+    pub entity: Entity,
+    pub amount: u32,
+    pub current: u32,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SkillPointsInsufficientEvent {
+    pub entity: Entity,
+    pub required: u32,
+    pub available: u32,
+}
+
+fn skill_point_cap(
+    attribute_queries: &mut AttributeQueries,
+    attribute_evaluator: &mut AttributeEvaluator,
+    cap: Entity,
+) -> u32 {
+    attribute_evaluator
+        .fetch_value(attribute_queries, cap)
+        .map_or(0, |cap| cap.to_f64().floor().max(0.0) as u32)
+}
+
+fn add_skill_points(
+    In((team, amount)): In<(Entity, u32)>,
+    mut pools: Query<&mut SkillPoints>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) -> Result<(u32, u32), SkillPointError> {
+    let mut pool = pools
+        .get_mut(team)
+        .map_err(|_| SkillPointError::MissingPool)?;
+    let cap = skill_point_cap(&mut attribute_queries, &mut attribute_evaluator, pool.cap);
+    let previous = pool.current;
+    pool.current = previous.saturating_add(amount).min(cap.max(previous));
+    Ok((pool.current - previous, pool.current))
+}
+
+/// Adds up to `amount` Skill Points to `team`, returning the new total.
+pub fn generate_skill_points(
+    world: &mut World,
+    team: Entity,
+    amount: u32,
+) -> Result<u32, SkillPointError> {
+    let (gained, current) = world
+        .run_system_cached_with(add_skill_points, (team, amount))
+        .map_err(|_| SkillPointError::SystemFailed)??;
+    world.trigger(SkillPointsGeneratedEvent {
+        entity: team,
+        amount: gained,
+        current,
+    });
+    Ok(current)
+}
+
+/// Removes `amount` Skill Points from `team`, returning the new total, or
+/// triggers [`SkillPointsInsufficientEvent`] and leaves the pool unchanged.
+pub fn consume_skill_points(
+    world: &mut World,
+    team: Entity,
+    amount: u32,
+) -> Result<u32, SkillPointError> {
+    let mut pool = world
+        .get_mut::<SkillPoints>(team)
+        .ok_or(SkillPointError::MissingPool)?;
+    if pool.current < amount {
+        let available = pool.current;
+        world.trigger(SkillPointsInsufficientEvent {
+            entity: team,
+            required: amount,
+            available,
+        });
+        return Err(SkillPointError::Insufficient {
+            required: amount,
+            available,
+        });
+    }
+    pool.current -= amount;
+    let current = pool.current;
+    world.trigger(SkillPointsConsumedEvent {
+        entity: team,
+        amount,
+        current,
+    });
+    Ok(current)
+}
+
+/// Settles the Skill Points of an action by `actor`: Basic attacks generate a
+/// Skill Point for its team and Skills consume one. Returns the team's new
+/// total, or `None` when the actor has no team or its team has no pool.
+pub fn settle_skill_points(
+    world: &mut World,
+    actor: Entity,
+    kind: ActionKind,
+) -> Result<Option<u32>, SkillPointError> {
+    let Some(team) = world
+        .get::<TeamMember>(actor)
+        .map(|team_member| team_member.0)
+    else {
+        return Ok(None);
+    };
+    let result = match kind {
+        ActionKind::Basic => generate_skill_points(world, team, 1),
+        ActionKind::Skill => consume_skill_points(world, team, 1),
+        ActionKind::Ultimate | ActionKind::FollowUp | ActionKind::Counter => {
+            return Ok(None);
+        }
+    };
+    match result {
+        Ok(current) => Ok(Some(current)),
+        Err(SkillPointError::MissingPool) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Drops points above the cap once a cap buff runs out.
+pub fn clamp_skill_points(
+    mut pools: Query<&mut SkillPoints>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) {
+    for mut pool in &mut pools {
+        let cap = skill_point_cap(&mut attribute_queries, &mut attribute_evaluator, pool.cap);
+        if pool.current > cap {
+            pool.current = cap;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, AttributeType, Modifier, Scalar};
+    use crate::combat::{ActionUsedEvent, CombatPlugin, use_action};
+
+    #[derive(Resource, Default)]
+    struct Log {
+        generated: Vec<u32>,
+        insufficient: usize,
+        skills: usize,
+    }
+
+    #[test]
+    fn test_skill_point_pool() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Log>();
+        app.add_observer(
+            |event: On<SkillPointsGeneratedEvent>, mut log: ResMut<Log>| {
+                log.generated.push(event.amount);
+            },
+        );
+        app.add_observer(
+            |_: On<SkillPointsInsufficientEvent>, mut log: ResMut<Log>| log.insufficient += 1,
+        );
+        app.add_observer(|event: On<ActionUsedEvent>, mut log: ResMut<Log>| {
+            if event.kind == ActionKind::Skill {
+                log.skills += 1;
+            }
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let cap = world
+            .spawn((
+                Attribute::Plain(Scalar::from_f64(5.0)),
+                AttributeType::SkillPointCap,
+            ))
+            .id();
+        world.spawn(Modifier::new(cap, Scalar::ONE, Scalar::ZERO));
+        let team = world.spawn(SkillPoints::new(cap)).id();
+        let character = world.spawn(TeamMember(team)).id();
+        app.update();
+
+        let act = |app: &mut App, kind: ActionKind| {
+            app.world_mut()
+                .commands()
+                .entity(character)
+                .queue(use_action(kind));
+            app.world_mut().flush();
+            app.world().get::<SkillPoints>(team).unwrap().current
+        };
+        assert_eq!(act(&mut app, ActionKind::Basic), 4);
+        assert_eq!(act(&mut app, ActionKind::Basic), 5);
+        assert_eq!(act(&mut app, ActionKind::Basic), 5);
+
+        let cap_buff = app
+            .world_mut()
+            .spawn(Modifier::new(cap, Scalar::ZERO, Scalar::from_f64(2.0)))
+            .id();
+        app.update();
+        assert_eq!(act(&mut app, ActionKind::Basic), 6);
+        app.world_mut().despawn(cap_buff);
+        app.update();
+        assert_eq!(app.world().get::<SkillPoints>(team).unwrap().current, 5);
+
+        for expected in (0..5).rev() {
+            assert_eq!(act(&mut app, ActionKind::Skill), expected);
+        }
+        assert_eq!(act(&mut app, ActionKind::Skill), 0);
+        assert_eq!(
+            consume_skill_points(app.world_mut(), team, 2),
+            Err(SkillPointError::Insufficient {
+                required: 2,
+                available: 0
+            })
+        );
+        assert_eq!(
+            generate_skill_points(app.world_mut(), character, 1),
+            Err(SkillPointError::MissingPool)
+        );
+
+        let log = app.world().resource::<Log>();
+        assert_eq!(log.generated, vec![1, 1, 0, 1]);
+        assert_eq!(log.insufficient, 2);
+        assert_eq!(log.skills, 5);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::attribute::{AttributeEvaluator, AttributeQueries, Number, Scalar};
+use crate::combat::advance_battle_clock;
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+/// Distance every combatant covers between two of its turns.
+pub fn action_gauge_length() -> Scalar {
+    Scalar::from_f64(10000.0)
+}
+
+/// Position of a combatant on the action timeline. The remaining `distance`
+/// is covered at the value of the `speed` attribute, so a speed change
+/// rescales the remaining action value while keeping the distance.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[component(on_add = action_gauge_on_add)]
+pub struct ActionGauge {
+    pub speed: Entity,
+    pub distance: Scalar,
+}
+
+impl ActionGauge {
+    pub fn new(speed: Entity) -> Self {
+        Self {
+            speed,
+            distance: action_gauge_length(),
+        }
+    }
+
+    pub fn action_value(&self, speed: Scalar) -> Option<Scalar> {
+        (speed > Scalar::ZERO).then(|| self.distance / speed)
+    }
+}
+
+/// Order in which combatants joined the timeline, used to break action value
+/// ties. Inserted along with the first [`ActionGauge`] of an entity.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ActionOrder(pub u64);
+
+#[derive(Resource, Default)]
+struct ActionOrderCounter(u64);
+
+fn action_gauge_on_add(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    world.commands().queue(move |world: &mut World| {
+        let mut counter = world.get_resource_or_init::<ActionOrderCounter>();
+        let order = ActionOrder(counter.0);
+        counter.0 += 1;
+        if let Ok(mut entity) = world.get_entity_mut(entity) {
+            entity.insert(order);
+        }
+    });
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct NextActorEvent {
+    pub entity: Entity,
+    /// Action value elapsed since the previous actor was selected.
+    pub action_value: Scalar,
+}
+
+/// Moves every gauge forward until one reaches the end and resets that one.
+/// Ties go to the combatant with the lowest [`ActionOrder`].
+pub fn select_next_actor(
+    mut gauges: Query<(Entity, &mut ActionGauge, Option<&ActionOrder>)>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) -> Option<NextActorEvent> {
+    let speeds = gauges
+        .iter()
+        .filter_map(|(entity, gauge, order)| {
+            let speed = attribute_evaluator.fetch_value(&mut attribute_queries, gauge.speed)?;
+            let order = order.map_or(u64::MAX, |order| order.0);
+            Some((entity, gauge.action_value(speed)?, speed, order))
+        })
+        .collect::<Vec<_>>();
+    let (actor, action_value, _, _) = *speeds.iter().min_by(|a, b| {
+        a.1.total_cmp(&b.1)
+            .then_with(|| a.3.cmp(&b.3))
+            .then_with(|| a.0.index().cmp(&b.0.index()))
+    })?;
+    for (entity, _, speed, _) in speeds {
+        let (_, mut gauge, _) = gauges.get_mut(entity).unwrap();
+        if entity == actor {
+            gauge.distance = action_gauge_length();
+            continue;
+        }
+        gauge.distance -= action_value * speed;
+        if gauge.distance < Scalar::ZERO {
+            gauge.distance = Scalar::ZERO;
+        }
+    }
+    Some(NextActorEvent {
+        entity: actor,
+        action_value,
+    })
+}
+
+/// Selects the next actor, advances the [`BattleClock`](crate::combat::BattleClock)
+/// and triggers [`NextActorEvent`] for the actor.
+pub fn next_actor(world: &mut World) -> Option<Entity> {
+    let event = world.run_system_cached(select_next_actor).ok()??;
+    advance_battle_clock(world, event.action_value);
+    world.trigger(event);
+    Some(event.entity)
+}
+
+/// Moves a combatant along its action gauge. Gauge shifts are fractions of
+/// [`action_gauge_length`]; action value shifts are converted to distance at
+/// the combatant's current speed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActionShift {
+    AdvanceGauge(Scalar),
+    DelayGauge(Scalar),
+    AdvanceActionValue(Scalar),
+    DelayActionValue(Scalar),
+    ActImmediately,
+}
+
+pub fn apply_action_shift(
+    In((entity, shift)): In<(Entity, ActionShift)>,
+    mut gauges: Query<&mut ActionGauge>,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) {
+    let Ok(mut gauge) = gauges.get_mut(entity) else {
+        return;
+    };
+    let mut speed = || {
+        attribute_evaluator
+            .fetch_value(&mut attribute_queries, gauge.speed)
+            .unwrap_or(Scalar::ZERO)
+    };
+    let distance = match shift {
+        ActionShift::AdvanceGauge(ratio) => gauge.distance - ratio * action_gauge_length(),
+        ActionShift::DelayGauge(ratio) => gauge.distance + ratio * action_gauge_length(),
+        ActionShift::AdvanceActionValue(action_value) => gauge.distance - action_value * speed(),
+        ActionShift::DelayActionValue(action_value) => gauge.distance + action_value * speed(),
+        ActionShift::ActImmediately => Scalar::ZERO,
+    };
+    gauge.distance = if distance < Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        distance
+    };
+}
+
+/// Entity command applying an [`ActionShift`] to the combatant.
+pub fn shift_action(shift: ActionShift) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let id = entity.id();
+        entity.world_scope(|world| {
+            let _ = world.run_system_cached_with(apply_action_shift, (id, shift));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, Modifier, ModifierValue};
+
+    #[derive(Resource, Default)]
+    struct Turns(Vec<(Entity, Scalar)>);
+
+    #[test]
+    fn test_timeline_follows_speed() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.init_resource::<Turns>();
+        app.add_observer(|event: On<NextActorEvent>, mut turns: ResMut<Turns>| {
+            turns.0.push((event.entity, event.action_value));
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let mut spawn_combatant = |speed: Scalar| {
+            let speed_attribute = world.spawn(Attribute::Plain(speed)).id();
+            let speed_modifier = world
+                .spawn(Modifier::new(speed_attribute, Scalar::ONE, Scalar::ZERO))
+                .id();
+            let combatant = world.spawn(ActionGauge::new(speed_attribute)).id();
+            (combatant, speed_modifier)
+        };
+        let (fast, _) = spawn_combatant(Scalar::from_f64(160.0));
+        let (slow, slow_speed_modifier) = spawn_combatant(Scalar::from_f64(100.0));
+        let (tied, _) = spawn_combatant(Scalar::from_f64(100.0));
+        app.update();
+
+        let advance = |app: &mut App| {
+            next_actor(app.world_mut());
+            *app.world().resource::<Turns>().0.last().unwrap()
+        };
+        assert_eq!(advance(&mut app), (fast, Scalar::from_f64(62.5)));
+        assert_eq!(advance(&mut app), (slow, Scalar::from_f64(37.5)));
+        assert_eq!(advance(&mut app), (tied, Scalar::ZERO));
+        assert_eq!(advance(&mut app), (fast, Scalar::from_f64(25.0)));
+
+        // `slow` has 7500 distance left at 125 AV; +50% speed covers it in 50 AV.
+        app.world_mut()
+            .entity_mut(slow_speed_modifier)
+            .insert(ModifierValue {
+                ratio: Scalar::from_f64(1.5),
+                delta: Scalar::ZERO,
+            });
+        app.update();
+        assert_eq!(advance(&mut app), (slow, Scalar::from_f64(50.0)));
+        assert_eq!(advance(&mut app), (fast, Scalar::from_f64(12.5)));
+        assert_eq!(advance(&mut app), (tied, Scalar::from_f64(12.5)));
+
+        // A combatant reusing a despawned entity's index still acts after
+        // the one that joined the timeline before it.
+        let world = app.world_mut();
+        let late_speed = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn(Modifier::new(late_speed, Scalar::ONE, Scalar::ZERO));
+        world.despawn(fast);
+        let late = world.spawn(ActionGauge::new(late_speed)).id();
+        assert!(late.index() < tied.index());
+        world.entity_mut(tied).insert(ActionGauge::new(late_speed));
+        world.despawn(slow);
+        app.update();
+        assert_eq!(advance(&mut app), (tied, Scalar::from_f64(100.0)));
+        assert_eq!(advance(&mut app), (late, Scalar::ZERO));
+    }
+
+    #[test]
+    fn test_action_advance_and_delay() {
+        let mut app = App::new();
+        app.add_plugins(AttributePlugin);
+        app.init_resource::<Turns>();
+        app.add_observer(|event: On<NextActorEvent>, mut turns: ResMut<Turns>| {
+            turns.0.push((event.entity, event.action_value));
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let speed = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+        world.spawn(Modifier::new(speed, Scalar::ONE, Scalar::ZERO));
+        let combatant = world.spawn(ActionGauge::new(speed)).id();
+        let other_speed = world.spawn(Attribute::Plain(Scalar::from_f64(50.0))).id();
+        world.spawn(Modifier::new(other_speed, Scalar::ONE, Scalar::ZERO));
+        let other = world.spawn(ActionGauge::new(other_speed)).id();
+        app.update();
+
+        let shift = |app: &mut App, entity: Entity, shift: ActionShift| {
+            app.world_mut()
+                .commands()
+                .entity(entity)
+                .queue(shift_action(shift));
+            app.world_mut().flush();
+            app.world().get::<ActionGauge>(entity).unwrap().distance
+        };
+        assert_eq!(
+            shift(
+                &mut app,
+                combatant,
+                ActionShift::AdvanceGauge(Scalar::from_f64(0.25))
+            ),
+            Scalar::from_f64(7500.0)
+        );
+        app.world_mut()
+            .spawn(Modifier::new(speed, Scalar::from_f64(0.5), Scalar::ZERO));
+        app.update();
+        assert_eq!(
+            shift(
+                &mut app,
+                combatant,
+                ActionShift::DelayActionValue(Scalar::from_f64(10.0))
+            ),
+            Scalar::from_f64(9000.0)
+        );
+        assert_eq!(
+            shift(
+                &mut app,
+                combatant,
+                ActionShift::AdvanceActionValue(Scalar::from_f64(80.0))
+            ),
+            Scalar::ZERO
+        );
+        assert_eq!(
+            shift(
+                &mut app,
+                combatant,
+                ActionShift::DelayGauge(Scalar::from_f64(0.3))
+            ),
+            Scalar::from_f64(3000.0)
+        );
+        assert_eq!(
+            shift(&mut app, other, ActionShift::ActImmediately),
+            Scalar::ZERO
+        );
+
+        next_actor(app.world_mut());
+        next_actor(app.world_mut());
+        assert_eq!(
+            app.world().resource::<Turns>().0,
+            vec![(other, Scalar::ZERO), (combatant, Scalar::from_f64(20.0))]
+        );
+    }
+}
@@ -0,0 +1,649 @@
+use crate::attribute::{
+    AttributeEvaluator, AttributeQueries, AttributeType, EvaluationContext, Modifier, Number,
+    Scalar,
+};
+use crate::combat::{
+    ActionGauge, ActionShift, CombatantAttributes, DamageEvent, DamageKind, DamageRequest, Element,
+    Level, TurnStartEvent, WeaknessBroken, deal_damage, end_turn, shift_action,
+};
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Toughness {
+    pub current: Scalar,
+    pub max: Scalar,
+}
+
+impl Toughness {
+    pub fn new(max: Scalar) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Break damage multiplier of the bar, `0.5 + max / 40`.
+    pub fn break_multiplier(&self) -> Scalar {
+        Scalar::from_f64(0.5) + self.max / Scalar::from_f64(40.0)
+    }
+}
+
+/// Elite or boss enemy. Breaking it inflicts more Wind Shear stacks, and its
+/// Bleed takes a smaller share of its Max HP.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Elite;
+
+/// Elements whose abilities reduce the enemy's [`Toughness`].
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Weaknesses(pub Vec<Element>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BreakEffect {
+    Bleed,
+    Burn,
+    Freeze,
+    Shock,
+    WindShear,
+    Entanglement,
+    Imprisonment,
+}
+
+impl BreakEffect {
+    /// Turns of the afflicted enemy the effect lasts.
+    pub fn duration(self) -> u32 {
+        match self {
+            BreakEffect::Bleed
+            | BreakEffect::Burn
+            | BreakEffect::Shock
+            | BreakEffect::WindShear => 2,
+            BreakEffect::Freeze | BreakEffect::Entanglement | BreakEffect::Imprisonment => 1,
+        }
+    }
+
+    /// Stacks inflicted by the break: 3 Wind Shear stacks on an [`Elite`],
+    /// 1 otherwise.
+    pub fn initial_stacks(self, elite: bool) -> u32 {
+        match self {
+            BreakEffect::WindShear if elite => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn max_stacks(self) -> u32 {
+        match self {
+            BreakEffect::WindShear | BreakEffect::Entanglement => 5,
+            _ => 1,
+        }
+    }
+
+    /// Damage per stack dealt at the start of each of the enemy's turns,
+    /// before the attacker's Break Effect and the enemy's factors. Bleed takes
+    /// 16% of the enemy's Max HP, 7% for an [`Elite`], capped by the level and
+    /// toughness multipliers.
+    pub fn damage(
+        self,
+        level_multiplier: Scalar,
+        toughness: &Toughness,
+        max_hp: Scalar,
+        elite: bool,
+    ) -> Scalar {
+        let toughness_multiplier = toughness.break_multiplier();
+        match self {
+            BreakEffect::Bleed => {
+                let ratio = if elite { 0.07 } else { 0.16 };
+                let bleed = Scalar::from_f64(ratio) * max_hp;
+                let cap = Scalar::from_f64(2.0) * level_multiplier * toughness_multiplier;
+                if bleed < cap { bleed } else { cap }
+            }
+            BreakEffect::Burn | BreakEffect::WindShear | BreakEffect::Freeze => level_multiplier,
+            BreakEffect::Shock => Scalar::from_f64(2.0) * level_multiplier,
+            BreakEffect::Entanglement => {
+                Scalar::from_f64(0.6) * level_multiplier * toughness_multiplier
+            }
+            BreakEffect::Imprisonment => Scalar::ZERO,
+        }
+    }
+
+    /// Speed ratio the enemy loses while the effect lasts.
+    pub fn speed_ratio(self) -> Scalar {
+        match self {
+            BreakEffect::Imprisonment => Scalar::from_f64(-0.1),
+            _ => Scalar::ZERO,
+        }
+    }
+}
+
+impl Element {
+    pub fn break_effect(self) -> BreakEffect {
+        match self {
+            Element::Physical => BreakEffect::Bleed,
+            Element::Fire => BreakEffect::Burn,
+            Element::Ice => BreakEffect::Freeze,
+            Element::Lightning => BreakEffect::Shock,
+            Element::Wind => BreakEffect::WindShear,
+            Element::Quantum => BreakEffect::Entanglement,
+            Element::Imaginary => BreakEffect::Imprisonment,
+        }
+    }
+
+    /// Break damage multiplier of the element.
+    pub fn break_multiplier(self) -> Scalar {
+        Scalar::from_f64(match self {
+            Element::Physical | Element::Fire => 2.0,
+            Element::Ice | Element::Lightning => 1.0,
+            Element::Wind => 1.5,
+            Element::Quantum | Element::Imaginary => 0.5,
+        })
+    }
+
+    /// Action delay, as a fraction of the gauge, of breaking the element with
+    /// `break_effect` Break Effect: 25%, plus 20% for Quantum and 30% for
+    /// Imaginary scaled by `1 + break_effect`.
+    pub fn break_delay(self, break_effect: Scalar) -> Scalar {
+        let extra = match self {
+            Element::Quantum => 0.2,
+            Element::Imaginary => 0.3,
+            _ => 0.0,
+        };
+        Scalar::from_f64(0.25) + Scalar::from_f64(extra) * (Scalar::ONE + break_effect)
+    }
+}
+
+/// Break effect inflicted by the last weakness break. It deals its damage for
+/// each stack at the start of each of the enemy's turns until `turns` runs
+/// out; a frozen enemy's turn is ended right away and its action advanced by
+/// 50%. Each direct hit on an entangled enemy adds a stack.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[component(on_replace = active_break_effect_on_replace)]
+pub struct ActiveBreakEffect {
+    pub effect: BreakEffect,
+    pub attacker: Entity,
+    pub element: Element,
+    /// Damage per turn and stack before the attacker's and enemy's factors.
+    pub damage: Scalar,
+    pub stacks: u32,
+    pub turns: u32,
+    /// Speed modifier of the effect, despawned along with it.
+    pub speed_modifier: Option<Entity>,
+}
+
+fn active_break_effect_on_replace(
+    mut world: DeferredWorld,
+    HookContext { entity, .. }: HookContext,
+) {
+    if let Some(speed_modifier) = world
+        .get::<ActiveBreakEffect>(entity)
+        .unwrap()
+        .speed_modifier
+    {
+        world.commands().entity(speed_modifier).try_despawn();
+    }
+}
+
+/// Break damage level multipliers of the attacker. Levels missing from the
+/// table use the closest lower level, or the lowest one.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct BreakLevelMultipliers(pub BTreeMap<u32, Scalar>);
+
+impl Default for BreakLevelMultipliers {
+    fn default() -> Self {
+        Self(BTreeMap::from([(80, Scalar::from_f64(3767.5533))]))
+    }
+}
+
+impl BreakLevelMultipliers {
+    pub fn get(&self, level: u32) -> Scalar {
+        self.0
+            .range(..=level)
+            .next_back()
+            .or_else(|| self.0.iter().next())
+            .map_or(Scalar::ZERO, |(_, multiplier)| *multiplier)
+    }
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct ToughnessReducedEvent {
+    pub entity: Entity,
+    pub attacker: Entity,
+    /// Toughness actually removed after scaling.
+    pub amount: Scalar,
+    pub current: Scalar,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+pub struct WeaknessBreakEvent {
+    pub entity: Entity,
+    pub attacker: Entity,
+    pub element: Element,
+    pub effect: BreakEffect,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ToughnessRecoveredEvent {
+    pub entity: Entity,
+}
+
+fn apply_toughness_reduction(
+    In((target, attacker, element, amount)): In<(Entity, Entity, Element, Scalar)>,
+    mut enemies: Query<(&mut Toughness, &Weaknesses), Without<WeaknessBroken>>,
+    combatant_attributes: CombatantAttributes,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) -> Option<(Scalar, Toughness)> {
+    let (mut toughness, weaknesses) = enemies.get_mut(target).ok()?;
+    if !weaknesses.0.contains(&element) {
+        return None;
+    }
+    let efficiency = combatant_attributes.value(
+        &mut attribute_queries,
+        &mut attribute_evaluator,
+        attacker,
+        AttributeType::WeaknessBreakEfficiency,
+        &EvaluationContext::new(attacker, target),
+    );
+    let previous = toughness.current;
+    let current = previous - amount * (Scalar::ONE + efficiency);
+    toughness.current = if current < Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        current
+    };
+    Some((previous - toughness.current, *toughness))
+}
+
+/// Entity command reducing the enemy's toughness by `amount`, scaled by the
+/// attacker's Weakness Break Efficiency, when `element` is one of its
+/// [`Weaknesses`]. Emptying the bar breaks the enemy: it is delayed, afflicted
+/// with the element's [`BreakEffect`] and takes break damage.
+pub fn reduce_toughness(attacker: Entity, element: Element, amount: Scalar) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let target = entity.id();
+        entity.world_scope(|world| {
+            let Ok(Some((amount, toughness))) = world.run_system_cached_with(
+                apply_toughness_reduction,
+                (target, attacker, element, amount),
+            ) else {
+                return;
+            };
+            world.trigger(ToughnessReducedEvent {
+                entity: target,
+                attacker,
+                amount,
+                current: toughness.current,
+            });
+            if toughness.current <= Scalar::ZERO {
+                break_weakness(world, target, attacker, element, toughness);
+            }
+        });
+    }
+}
+
+/// The attacker's Break Effect and the target's Max HP.
+fn break_stats(
+    In((attacker, target)): In<(Entity, Entity)>,
+    combatant_attributes: CombatantAttributes,
+    mut attribute_queries: AttributeQueries,
+    mut attribute_evaluator: ResMut<AttributeEvaluator>,
+) -> (Scalar, Scalar) {
+    let context = EvaluationContext::new(attacker, target);
+    let mut stat = |owner, attribute_type| {
+        combatant_attributes.value(
+            &mut attribute_queries,
+            &mut attribute_evaluator,
+            owner,
+            attribute_type,
+            &context,
+        )
+    };
+    (
+        stat(attacker, AttributeType::BreakDamage),
+        stat(target, AttributeType::MaxHP),
+    )
+}
+
+fn break_weakness(
+    world: &mut World,
+    target: Entity,
+    attacker: Entity,
+    element: Element,
+    toughness: Toughness,
+) {
+    let effect = element.break_effect();
+    let elite = world.entity(target).contains::<Elite>();
+    let (break_effect, max_hp) = world
+        .run_system_cached_with(break_stats, (attacker, target))
+        .unwrap_or((Scalar::ZERO, Scalar::ZERO));
+    let level = world.get::<Level>(attacker).map_or(80, |level| level.0);
+    let level_multiplier = world
+        .get_resource::<BreakLevelMultipliers>()
+        .map_or(Scalar::ZERO, |multipliers| multipliers.get(level));
+
+    let speed_ratio = effect.speed_ratio();
+    let speed_modifier = world
+        .get::<ActionGauge>(target)
+        .filter(|_| speed_ratio != Scalar::ZERO)
+        .map(|gauge| gauge.speed)
+        .map(|speed| {
+            world
+                .spawn(Modifier::new(speed, speed_ratio, Scalar::ZERO))
+                .id()
+        });
+    world.entity_mut(target).insert((
+        WeaknessBroken,
+        ActiveBreakEffect {
+            effect,
+            attacker,
+            element,
+            damage: effect.damage(level_multiplier, &toughness, max_hp, elite),
+            stacks: effect.initial_stacks(elite),
+            turns: effect.duration(),
+            speed_modifier,
+        },
+    ));
+    shift_action(ActionShift::DelayGauge(element.break_delay(break_effect)))
+        .apply(world.entity_mut(target));
+    world.trigger(WeaknessBreakEvent {
+        entity: target,
+        attacker,
+        element,
+        effect,
+    });
+
+    let base = element.break_multiplier() * level_multiplier * toughness.break_multiplier();
+    deal_damage(DamageRequest::break_damage(attacker, target, element, base)).apply(world);
+}
+
+/// At the start of a combatant's turn its [`ActiveBreakEffect`] deals its
+/// damage and counts down, then a broken enemy recovers its toughness.
+pub fn resolve_break_at_turn_start(
+    event: On<TurnStartEvent>,
+    mut enemies: Query<(
+        Option<&mut ActiveBreakEffect>,
+        Option<&mut Toughness>,
+        Has<WeaknessBroken>,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = event.entity;
+    let Ok((active_break_effect, toughness, broken)) = enemies.get_mut(entity) else {
+        return;
+    };
+    if let Some(mut active_break_effect) = active_break_effect {
+        if active_break_effect.damage > Scalar::ZERO {
+            commands.queue(deal_damage(
+                DamageRequest::break_damage(
+                    active_break_effect.attacker,
+                    entity,
+                    active_break_effect.element,
+                    active_break_effect.damage
+                        * Scalar::from_f64(f64::from(active_break_effect.stacks)),
+                )
+                .with_kind(DamageKind::BreakEffect),
+            ));
+        }
+        if active_break_effect.effect == BreakEffect::Freeze {
+            commands
+                .entity(entity)
+                .queue(shift_action(ActionShift::AdvanceGauge(Scalar::from_f64(
+                    0.5,
+                ))));
+            commands.queue(end_turn);
+        }
+        active_break_effect.turns = active_break_effect.turns.saturating_sub(1);
+        if active_break_effect.turns == 0 {
+            commands.entity(entity).remove::<ActiveBreakEffect>();
+        }
+    }
+    if broken && let Some(mut toughness) = toughness {
+        toughness.current = toughness.max;
+        commands.entity(entity).remove::<WeaknessBroken>();
+        commands.trigger(ToughnessRecoveredEvent { entity });
+    }
+}
+
+/// Adds an Entanglement stack for each direct hit on an entangled enemy.
+pub fn stack_entanglement_on_hit(
+    event: On<DamageEvent>,
+    mut active_break_effects: Query<&mut ActiveBreakEffect>,
+) {
+    if event.kind != DamageKind::Direct {
+        return;
+    }
+    if let Ok(mut active_break_effect) = active_break_effects.get_mut(event.entity)
+        && active_break_effect.effect == BreakEffect::Entanglement
+        && active_break_effect.stacks < active_break_effect.effect.max_stacks()
+    {
+        active_break_effect.stacks += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{
+        Attribute, AttributeOf, AttributePlugin, ModifierCondition, ModifierValue,
+    };
+    use crate::combat::{CombatPlugin, DamageEvent};
+
+    #[derive(Resource, Default)]
+    struct Log {
+        breaks: Vec<BreakEffect>,
+        damage: Vec<DamageEvent>,
+        recovered: usize,
+    }
+
+    #[test]
+    fn test_weakness_break() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Log>();
+        app.add_observer(|event: On<WeaknessBreakEvent>, mut log: ResMut<Log>| {
+            log.breaks.push(event.effect);
+        });
+        app.add_observer(|event: On<DamageEvent>, mut log: ResMut<Log>| {
+            log.damage.push(*event);
+        });
+        app.add_observer(|_: On<ToughnessRecoveredEvent>, mut log: ResMut<Log>| {
+            log.recovered += 1;
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let attacker = world.spawn(Level(80)).id();
+        for (attribute_type, value) in [
+            (
+                AttributeType::WeaknessBreakEfficiency,
+                Scalar::from_f64(0.5),
+            ),
+            (AttributeType::BreakDamage, Scalar::ONE),
+        ] {
+            let attribute = world
+                .spawn((
+                    Attribute::Plain(value),
+                    attribute_type,
+                    AttributeOf(attacker),
+                ))
+                .id();
+            world.spawn(Modifier::new(attribute, Scalar::ONE, Scalar::ZERO));
+        }
+        let mut spawn_enemy = |element: Element| {
+            let speed = world.spawn(Attribute::Plain(Scalar::from_f64(100.0))).id();
+            world.spawn(Modifier::new(speed, Scalar::ONE, Scalar::ZERO));
+            world
+                .spawn((
+                    Toughness::new(Scalar::from_f64(60.0)),
+                    Weaknesses(vec![element]),
+                    ActionGauge::new(speed),
+                ))
+                .id()
+        };
+        let enemy = spawn_enemy(Element::Fire);
+        let imprisoned = spawn_enemy(Element::Imaginary);
+        let sheared = spawn_enemy(Element::Wind);
+        let entangled = spawn_enemy(Element::Quantum);
+        world.entity_mut(sheared).insert(Elite);
+        // +25% DMG taken while broken, which the break damage itself already
+        // takes in the frame the enemy breaks.
+        let vulnerability = world
+            .spawn((
+                Attribute::Plain(Scalar::ZERO),
+                AttributeType::Vulnerability,
+                AttributeOf(enemy),
+            ))
+            .id();
+        world.spawn(Modifier::new(vulnerability, Scalar::ONE, Scalar::ZERO));
+        let broken_condition = ModifierCondition::has_component::<WeaknessBroken>(world, enemy);
+        world.spawn((
+            Modifier::new(vulnerability, Scalar::ZERO, Scalar::from_f64(0.25)),
+            broken_condition,
+        ));
+        app.update();
+
+        let hit = |app: &mut App, enemy: Entity, element: Element| {
+            app.world_mut()
+                .commands()
+                .entity(enemy)
+                .queue(reduce_toughness(attacker, element, Scalar::from_f64(20.0)));
+            app.world_mut().flush();
+            app.world().get::<Toughness>(enemy).unwrap().current
+        };
+        let start_turn = |app: &mut App, enemy: Entity| {
+            app.world_mut().trigger(TurnStartEvent {
+                entity: enemy,
+                extra: false,
+            });
+            app.world_mut().flush();
+        };
+        assert_eq!(hit(&mut app, enemy, Element::Ice), Scalar::from_f64(60.0));
+        assert_eq!(hit(&mut app, enemy, Element::Fire), Scalar::from_f64(30.0));
+        assert_eq!(hit(&mut app, enemy, Element::Fire), Scalar::ZERO);
+        assert!(app.world().entity(enemy).contains::<WeaknessBroken>());
+        assert_eq!(
+            app.world().get::<ActionGauge>(enemy).unwrap().distance,
+            Scalar::from_f64(12500.0)
+        );
+        assert_eq!(hit(&mut app, enemy, Element::Fire), Scalar::ZERO);
+
+        start_turn(&mut app, enemy);
+        assert_eq!(
+            app.world().get::<Toughness>(enemy).unwrap().current,
+            Scalar::from_f64(60.0)
+        );
+        assert!(!app.world().entity(enemy).contains::<WeaknessBroken>());
+        assert_eq!(
+            app.world().get::<ActiveBreakEffect>(enemy).unwrap().turns,
+            1
+        );
+        start_turn(&mut app, enemy);
+        assert!(!app.world().entity(enemy).contains::<ActiveBreakEffect>());
+
+        // Imaginary breaks delay by 25% + 30% × (1 + BE) and slow by 10%.
+        assert_eq!(
+            hit(&mut app, imprisoned, Element::Imaginary),
+            Scalar::from_f64(30.0)
+        );
+        assert_eq!(hit(&mut app, imprisoned, Element::Imaginary), Scalar::ZERO);
+        assert_eq!(
+            app.world().get::<ActionGauge>(imprisoned).unwrap().distance,
+            Scalar::from_f64(18500.0)
+        );
+        let active_break_effect = *app.world().get::<ActiveBreakEffect>(imprisoned).unwrap();
+        assert_eq!(active_break_effect.effect, BreakEffect::Imprisonment);
+        let speed_modifier = active_break_effect.speed_modifier.unwrap();
+        assert_eq!(
+            app.world().get::<ModifierValue>(speed_modifier),
+            Some(&ModifierValue {
+                ratio: Scalar::from_f64(-0.1),
+                delta: Scalar::ZERO
+            })
+        );
+        start_turn(&mut app, imprisoned);
+        assert!(
+            !app.world()
+                .entity(imprisoned)
+                .contains::<ActiveBreakEffect>()
+        );
+        assert!(app.world().get_entity(speed_modifier).is_err());
+
+        let log = app.world().resource::<Log>();
+        assert_eq!(
+            log.breaks,
+            vec![BreakEffect::Burn, BreakEffect::Imprisonment]
+        );
+        assert_eq!(log.recovered, 2);
+        let kinds = log
+            .damage
+            .iter()
+            .map(|damage| damage.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                DamageKind::Break,
+                DamageKind::BreakEffect,
+                DamageKind::BreakEffect,
+                DamageKind::Break,
+            ]
+        );
+        let breakdown = log.damage[0].breakdown;
+        assert!((breakdown.base.to_f64() - 2.0 * 3767.5533 * 2.0).abs() < 1e-2);
+        assert_eq!(breakdown.damage_boost, Scalar::from_f64(2.0));
+        assert_eq!(breakdown.vulnerability, Scalar::from_f64(1.25));
+        assert!((breakdown.broken.to_f64() - 0.9).abs() < 1e-6);
+        let expected = 2.0 * 3767.5533 * 2.0 * 2.0 * 1.25 * 0.9;
+        assert!((breakdown.total.to_f64() - expected).abs() < 1e-1);
+
+        // Burn deals 1× the level multiplier, at full damage while broken.
+        let burn = log.damage[1].breakdown;
+        assert!((burn.base.to_f64() - 3767.5533).abs() < 1e-2);
+        assert_eq!(burn.broken, Scalar::ONE);
+        assert_eq!(burn.vulnerability, Scalar::from_f64(1.25));
+        assert!((log.damage[2].breakdown.broken.to_f64() - 0.9).abs() < 1e-6);
+        assert_eq!(log.damage[2].breakdown.vulnerability, Scalar::ONE);
+
+        // Breaking an elite inflicts 3 Wind Shear stacks of 1× the level
+        // multiplier each.
+        hit(&mut app, sheared, Element::Wind);
+        hit(&mut app, sheared, Element::Wind);
+        let active_break_effect = *app.world().get::<ActiveBreakEffect>(sheared).unwrap();
+        assert_eq!(active_break_effect.effect, BreakEffect::WindShear);
+        assert_eq!(active_break_effect.stacks, 3);
+        start_turn(&mut app, sheared);
+        let wind_shear = app
+            .world()
+            .resource::<Log>()
+            .damage
+            .last()
+            .unwrap()
+            .breakdown;
+        assert!((wind_shear.base.to_f64() - 3.0 * 3767.5533).abs() < 1e-1);
+
+        // Each direct hit adds an Entanglement stack of 0.6× the level and
+        // toughness multipliers; break damage doesn't.
+        hit(&mut app, entangled, Element::Quantum);
+        hit(&mut app, entangled, Element::Quantum);
+        for _ in 0..2 {
+            app.world_mut()
+                .commands()
+                .queue(deal_damage(DamageRequest::new(
+                    attacker,
+                    entangled,
+                    Element::Quantum,
+                    Scalar::ONE,
+                )));
+            app.world_mut().flush();
+        }
+        assert_eq!(
+            app.world()
+                .get::<ActiveBreakEffect>(entangled)
+                .unwrap()
+                .stacks,
+            3
+        );
+        start_turn(&mut app, entangled);
+        let entanglement = *app.world().resource::<Log>().damage.last().unwrap();
+        assert_eq!(entanglement.kind, DamageKind::BreakEffect);
+        assert!((entanglement.breakdown.base.to_f64() - 3.0 * 0.6 * 3767.5533 * 2.0).abs() < 1e-1);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::combat::{next_actor, resolve_action_queue, settle_skill_points};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActionKind {
+    Basic,
+    Skill,
+    Ultimate,
+    FollowUp,
+    Counter,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Turn {
+    pub actor: Entity,
+    pub extra: bool,
+}
+
+/// The turn in progress and the extra turns waiting to be taken. Extra turns
+/// are taken before the timeline advances and leave every gauge untouched.
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct TurnState {
+    pub current: Option<Turn>,
+    pub extra_turns: VecDeque<Entity>,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TurnStartEvent {
+    pub entity: Entity,
+    pub extra: bool,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionUsedEvent {
+    pub entity: Entity,
+    pub kind: ActionKind,
+}
+
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TurnEndEvent {
+    pub entity: Entity,
+    pub extra: bool,
+}
+
+/// Command ending the current turn, if any, resolving the
+/// [`ActionQueue`](crate::combat::ActionQueue) and starting the next turn.
+pub fn start_next_turn(world: &mut World) {
+    end_turn(world);
+    resolve_action_queue(world);
+    let extra_turn = world
+        .get_resource_mut::<TurnState>()
+        .and_then(|mut turn_state| turn_state.extra_turns.pop_front());
+    let turn = match extra_turn {
+        Some(actor) => Turn { actor, extra: true },
+        None => {
+            let Some(actor) = next_actor(world) else {
+                return;
+            };
+            Turn {
+                actor,
+                extra: false,
+            }
+        }
+    };
+    if let Some(mut turn_state) = world.get_resource_mut::<TurnState>() {
+        turn_state.current = Some(turn);
+    }
+    world.trigger(TurnStartEvent {
+        entity: turn.actor,
+        extra: turn.extra,
+    });
+}
+
+/// Command ending the current turn, if any.
+pub fn end_turn(world: &mut World) {
+    let Some(turn) = world
+        .get_resource_mut::<TurnState>()
+        .and_then(|mut turn_state| turn_state.current.take())
+    else {
+        return;
+    };
+    world.trigger(TurnEndEvent {
+        entity: turn.actor,
+        extra: turn.extra,
+    });
+}
+
+/// Entity command queueing an extra turn for the combatant.
+pub fn grant_extra_turn(mut entity: EntityWorldMut) {
+    let actor = entity.id();
+    entity.world_scope(|world| {
+        if let Some(mut turn_state) = world.get_resource_mut::<TurnState>() {
+            turn_state.extra_turns.push_back(actor);
+        }
+    });
+}
+
+/// Entity command using an action of the combatant. Skill Points are settled
+/// first through [`settle_skill_points`]; a Skill the team can't afford is not
+/// used, so [`ActionUsedEvent`] is only triggered on success.
+pub fn use_action(kind: ActionKind) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        let actor = entity.id();
+        entity.world_scope(|world| {
+            if settle_skill_points(world, actor, kind).is_err() {
+                return;
+            }
+            world.trigger(ActionUsedEvent {
+                entity: actor,
+                kind,
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::{Attribute, AttributePlugin, Modifier, Number, Scalar};
+    use crate::combat::{ActionGauge, BattleClock, CombatPlugin};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(&'static str, Entity, bool)>);
+
+    #[test]
+    fn test_turn_lifecycle_and_extra_turns() {
+        let mut app = App::new();
+        app.add_plugins((AttributePlugin, CombatPlugin));
+        app.init_resource::<Log>();
+        app.add_observer(|event: On<TurnStartEvent>, mut log: ResMut<Log>| {
+            log.0.push(("start", event.entity, event.extra));
+        });
+        app.add_observer(|event: On<TurnEndEvent>, mut log: ResMut<Log>| {
+            log.0.push(("end", event.entity, event.extra));
+        });
+        app.finish();
+        app.cleanup();
+        let world = app.world_mut();
+
+        let mut spawn_combatant = |speed: Scalar| {
+            let speed_attribute = world.spawn(Attribute::Plain(speed)).id();
+            world.spawn(Modifier::new(speed_attribute, Scalar::ONE, Scalar::ZERO));
+            world.spawn(ActionGauge::new(speed_attribute)).id()
+        };
+        let fast = spawn_combatant(Scalar::from_f64(200.0));
+        let slow = spawn_combatant(Scalar::from_f64(80.0));
+        // A kit granting itself an extra turn after using its Skill.
+        app.world_mut().entity_mut(fast).observe(
+            |event: On<ActionUsedEvent>, mut commands: Commands| {
+                if event.kind == ActionKind::Skill {
+                    commands.entity(event.entity).queue(grant_extra_turn);
+                }
+            },
+        );
+        app.update();
+
+        start_next_turn(app.world_mut());
+        app.world_mut()
+            .commands()
+            .entity(fast)
+            .queue(use_action(ActionKind::Skill));
+        app.world_mut().flush();
+        let gauge = *app.world().get::<ActionGauge>(slow).unwrap();
+        start_next_turn(app.world_mut());
+        assert_eq!(*app.world().get::<ActionGauge>(slow).unwrap(), gauge);
+        assert_eq!(
+            app.world().resource::<BattleClock>().elapsed,
+            Scalar::from_f64(50.0)
+        );
+        app.world_mut()
+            .commands()
+            .entity(fast)
+            .queue(use_action(ActionKind::Basic));
+        start_next_turn(app.world_mut());
+        start_next_turn(app.world_mut());
+        end_turn(app.world_mut());
+
+        assert_eq!(
+            app.world().resource::<Log>().0,
+            vec![
+                ("start", fast, false),
+                ("end", fast, false),
+                ("start", fast, true),
+                ("end", fast, true),
+                ("start", fast, false),
+                ("end", fast, false),
+                ("start", slow, false),
+                ("end", slow, false),
+            ]
+        );
+        assert_eq!(app.world().resource::<TurnState>().current, None);
+    }
+}
@@ -1,5 +1,6 @@
-mod attribute;
-mod utils;
+pub mod attribute;
+pub mod combat;
+pub mod utils;
 
 #[cfg(test)]
 mod tests {
@@ -9,6 +10,8 @@ mod tests {
     use bevy::prelude::*;
     use std::fmt::Display;
 
+    type AttributeOrModifier = Or<(With<Attribute>, With<Modifier>)>;
+
     #[test]
     fn a() {
         let mut app = App::new();
@@ -42,64 +45,142 @@ mod tests {
         world.run_system_once(print_attributes).unwrap();
 
         // robin base modifier
-        world.spawn(Modifier::new(robin[0], 0.0, 640.0 + 635.0));
+        world.spawn(Modifier::new(
+            robin[0],
+            Scalar::ZERO,
+            Scalar::from_f64(640.0 + 635.0),
+        ));
         // robin delta modifier
         world.spawn(Modifier::new(
             robin[1],
-            0.12 + 0.432 * 2.0 + 0.116 + 0.112 + 0.12 + 0.12 + 0.28,
-            352.0 + 19.0,
+            Scalar::from_f64(0.12 + 0.432 * 2.0 + 0.116 + 0.112 + 0.12 + 0.12 + 0.28),
+            Scalar::from_f64(352.0 + 19.0),
         ));
 
         // danheng base modifier
-        world.spawn(Modifier::new(danheng[0], 0.0, 582.0 + 476.0));
+        world.spawn(Modifier::new(
+            danheng[0],
+            Scalar::ZERO,
+            Scalar::from_f64(582.0 + 476.0),
+        ));
         // danheng delta modifier
         world.spawn(Modifier::new(
             danheng[1],
-            0.432 * 2.0 + 0.159 + 0.086 + 0.077 + 0.125 + 0.28,
-            352.0 + 16.0 + 19.0 + 61.0 + 35.0,
+            Scalar::from_f64(0.432 * 2.0 + 0.159 + 0.086 + 0.077 + 0.125 + 0.28),
+            Scalar::from_f64(352.0 + 16.0 + 19.0 + 61.0 + 35.0),
         ));
 
         // phainon base modifier
-        world.spawn(Modifier::new(phainon[0], 0.0, 582.0 + 687.0 + 1.0));
+        world.spawn(Modifier::new(
+            phainon[0],
+            Scalar::ZERO,
+            Scalar::from_f64(582.0 + 687.0 + 1.0),
+        ));
         // phainon delta modifier
-        world.spawn(Modifier::new(phainon[1], 0.432 * 2.0, 352.0 + 21.0));
-        let phainon_talent_modifier = world.spawn(Modifier::new(phainon[1], 0.5, 0.0)).id(); // 照见英雄本色
-        world.spawn(Modifier::new(phainon[1], 0.12, 0.0));
+        world.spawn(Modifier::new(
+            phainon[1],
+            Scalar::from_f64(0.432 * 2.0),
+            Scalar::from_f64(352.0 + 21.0),
+        ));
+        let phainon_talent_modifier = world
+            .spawn(Modifier::new(
+                phainon[1],
+                Scalar::from_f64(0.5),
+                Scalar::ZERO,
+            ))
+            .id(); // 照见英雄本色
+        world.spawn(Modifier::new(
+            phainon[1],
+            Scalar::from_f64(0.12),
+            Scalar::ZERO,
+        ));
 
         world.spawn(DynamicModifier::new_scale(
-            phainon[2], danheng[3], 0.0, 0.0, 0.15,
+            phainon[2],
+            danheng[3],
+            Scalar::ZERO,
+            Scalar::ZERO,
+            Scalar::from_f64(0.15),
         )); // 神秀
 
         println!("initial modifiers added");
         world.run_system_once(print_attributes).unwrap();
 
-        let robin_lightcone_modifier = world.spawn(Modifier::new(robin[1], 0.48, 0.0)).id(); // 夜色流光溢彩
+        let robin_lightcone_modifier = world
+            .spawn(Modifier::new(
+                robin[1],
+                Scalar::from_f64(0.48),
+                Scalar::ZERO,
+            ))
+            .id(); // 夜色流光溢彩
 
         let robin_ultimate_modifiers = [
             world
                 .spawn(DynamicModifier::new_scale(
-                    robin[2], robin[3], 0.0, 0.0, 0.228,
+                    robin[2],
+                    robin[3],
+                    Scalar::ZERO,
+                    Scalar::ZERO,
+                    Scalar::from_f64(0.228),
+                ))
+                .id(),
+            world
+                .spawn(Modifier::new(
+                    robin[2],
+                    Scalar::ZERO,
+                    Scalar::from_f64(200.0),
                 ))
                 .id(),
-            world.spawn(Modifier::new(robin[2], 0.0, 200.0)).id(),
             world
                 .spawn(DynamicModifier::new_scale(
-                    danheng[2], robin[3], 0.0, 0.0, 0.228,
+                    danheng[2],
+                    robin[3],
+                    Scalar::ZERO,
+                    Scalar::ZERO,
+                    Scalar::from_f64(0.228),
+                ))
+                .id(),
+            world
+                .spawn(Modifier::new(
+                    danheng[2],
+                    Scalar::ZERO,
+                    Scalar::from_f64(200.0),
                 ))
                 .id(),
-            world.spawn(Modifier::new(danheng[2], 0.0, 200.0)).id(),
             world
                 .spawn(DynamicModifier::new_scale(
-                    phainon[2], robin[3], 0.0, 0.0, 0.228,
+                    phainon[2],
+                    robin[3],
+                    Scalar::ZERO,
+                    Scalar::ZERO,
+                    Scalar::from_f64(0.228),
+                ))
+                .id(),
+            world
+                .spawn(Modifier::new(
+                    phainon[2],
+                    Scalar::ZERO,
+                    Scalar::from_f64(200.0),
                 ))
                 .id(),
-            world.spawn(Modifier::new(phainon[2], 0.0, 200.0)).id(),
         ];
         println!("robin ultimate modifiers added");
         world.run_system_once(print_attributes).unwrap();
 
-        let phainon_suit_modifier = world.spawn(Modifier::new(phainon[1], 0.48, 0.0)).id(); // 船长
-        let phainon_ultimate_modifier = world.spawn(Modifier::new(phainon[1], 0.8, 0.0)).id(); // 此躯即神
+        let phainon_suit_modifier = world
+            .spawn(Modifier::new(
+                phainon[1],
+                Scalar::from_f64(0.48),
+                Scalar::ZERO,
+            ))
+            .id(); // 船长
+        let phainon_ultimate_modifier = world
+            .spawn(Modifier::new(
+                phainon[1],
+                Scalar::from_f64(0.8),
+                Scalar::ZERO,
+            ))
+            .id(); // 此躯即神
 
         println!("phainon ultimate modifiers added");
         world.run_system_once(print_attributes).unwrap();
@@ -111,8 +192,8 @@ mod tests {
         world
             .entity_mut(phainon_talent_modifier)
             .insert(ModifierValue {
-                ratio: 0.5 * 2.0,
-                delta: 0.0,
+                ratio: Scalar::from_f64(0.5 * 2.0),
+                delta: Scalar::ZERO,
             });
         println!("phainon talent modifier updated");
         world.run_system_once(print_attributes).unwrap();
@@ -133,8 +214,7 @@ mod tests {
 
         world
             .run_system_once(
-                |query: Query<Entity, Or<(With<Attribute>, With<Modifier>)>>,
-                 mut commands: Commands| {
+                |query: Query<Entity, AttributeOrModifier>, mut commands: Commands| {
                     for entity in query {
                         commands.entity(entity).despawn();
                     }
@@ -145,7 +225,10 @@ mod tests {
 
     fn spawn_attributes(world: &mut World, name: impl Display) -> [Entity; 5] {
         let base = world
-            .spawn((Attribute::Plain(0.0), Name::new(format!("{} Base", name))))
+            .spawn((
+                Attribute::Plain(Scalar::ZERO),
+                Name::new(format!("{} Base", name)),
+            ))
             .flush();
         let delta = world
             .spawn((
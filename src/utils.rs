@@ -11,21 +11,21 @@ unsafe impl<T: Default> SystemParam for FromDefault<T> {
     type State = ();
     type Item<'world, 'state> = FromDefault<T>;
 
-    fn init_state(world: &mut World) -> Self::State {}
+    fn init_state(_world: &mut World) -> Self::State {}
 
     fn init_access(
-        state: &Self::State,
-        system_meta: &mut SystemMeta,
-        component_access_set: &mut FilteredAccessSet,
-        world: &mut World,
+        _state: &Self::State,
+        _system_meta: &mut SystemMeta,
+        _component_access_set: &mut FilteredAccessSet,
+        _world: &mut World,
     ) {
     }
 
     unsafe fn get_param<'world, 'state>(
-        state: &'state mut Self::State,
-        system_meta: &SystemMeta,
-        world: UnsafeWorldCell<'world>,
-        change_tick: Tick,
+        _state: &'state mut Self::State,
+        _system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'world>,
+        _change_tick: Tick,
     ) -> Self::Item<'world, 'state> {
         FromDefault(T::default())
     }